@@ -18,7 +18,11 @@ fn main() -> anyhow::Result<()> {
     let handle = thread::spawn(move || {
         while let Ok(work) = receiver.recv() {
             let work = work_handler(work).unwrap();
-            executor.lock().unwrap().receive_work(work, None).unwrap();
+            executor
+                .lock()
+                .unwrap()
+                .receive_work(work, None, &[])
+                .unwrap();
         }
     });
 
@@ -29,7 +33,7 @@ fn main() -> anyhow::Result<()> {
     works_executor
         .lock()
         .unwrap()
-        .add_workflow(Arc::new(workflow));
+        .add_workflow(Arc::new(workflow))?;
 
     let args = std::env::args().collect::<Vec<String>>();
     let keyword = args.get(1).expect("please input keyword");
@@ -29,6 +29,39 @@ pub enum Error {
     #[error("receive error : {}", .0)]
     ReceiveError(#[from] std::sync::mpsc::RecvError),
 
+    #[error("cron error : {}", .0)]
+    CronError(#[from] cron::error::Error),
+
+    #[error("file watch error : {}", .0)]
+    FileWatchError(#[from] notify::Error),
+
+    #[error("glob pattern error : {}", .0)]
+    GlobError(#[from] glob::PatternError),
+
+    #[error("condition error : {}", .0)]
+    ConditionError(String),
+
+    #[error("workflow {} not found", .0)]
+    WorkflowNotFound(uuid::Uuid),
+
+    #[error("node {} not found", .0)]
+    NodeNotFound(uuid::Uuid),
+
+    #[error("link from {from} to missing node {to}")]
+    DanglingLink { from: uuid::Uuid, to: uuid::Uuid },
+
+    #[error("trigger {trigger_id} is already used by workflow {workflow_id}")]
+    TriggerCollision {
+        trigger_id: String,
+        workflow_id: uuid::Uuid,
+    },
+
+    #[error("workflow run cancelled")]
+    Cancelled,
+
+    #[error("http error : [{status}] {body}")]
+    HttpStatus { status: u16, body: String },
+
     #[error("command error : [{status}] {message}")]
     Command { status: ExitStatus, message: String },
 
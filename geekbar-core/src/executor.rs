@@ -1,7 +1,7 @@
 use std::{
-    collections::{HashMap, HashSet, VecDeque},
+    collections::{HashMap, HashSet},
     path::Path,
-    sync::Arc,
+    sync::{Arc, Mutex},
 };
 
 use crossbeam_channel::{Receiver, Sender};
@@ -10,17 +10,35 @@ use serde_json::{to_value, Value};
 use uuid::Uuid;
 
 use crate::{
+    cancel::CancellationToken,
+    frecency::{self, Frecency, PickScope},
+    hotkey::{HotkeyReceiver, HotkeyRegistry},
+    link::Modifier,
+    nodes::list_filter::ListOption,
+    nodes::trigger::Trigger,
     params::Params,
+    pool::WorkerPool,
+    prompt::Prompt,
+    scheduler::{ScheduleReceiver, Scheduler},
+    utils::{self, FuzzyMatcher, Matcher},
+    watcher::{FileWatchReceiver, FileWatcher},
     workflow::{Workflow, WorkflowBuilder},
 };
 
+// fuzzy scores within about one matched char of each other count as similar matches,
+// between those the keyword picked more often ranks first
+const MATCH_SCORE_BUCKET: i64 = 16;
+
 type WorkflowId = Uuid;
 type WorkflowPid = Uuid;
+type RunId = Uuid;
 type TriggerId = String;
 type NodeId = Uuid;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WorkNode {
+    // one execution, created when the trigger fires
+    run_id: RunId,
     workflow_pid: WorkflowPid,
     workflow_id: WorkflowId,
     node_id: NodeId,
@@ -33,6 +51,13 @@ pub struct Work {
 }
 
 impl Work {
+    /// Works asking the user which workflow a shared trigger should start.
+    pub fn is_chooser(&self) -> bool {
+        self.work_node.workflow_pid.is_nil()
+    }
+    pub fn run_id(&self) -> RunId {
+        self.work_node.run_id
+    }
     pub fn workflow_id(&self) -> WorkflowId {
         self.work_node.workflow_id
     }
@@ -48,31 +73,246 @@ impl Work {
     }
 }
 
+/// How well a keyword matches the input, better matches first.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+pub enum MatchKind {
+    Exact,
+    Prefix,
+    Fuzzy,
+    Title,
+}
+
+/// A keyword trigger matching a partial input, see `Executor::match_keywords`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TriggerCandidate {
+    pub trigger_id: TriggerId,
+    pub title: String,
+    pub workflow_id: WorkflowId,
+    pub workflow_title: String,
+    pub kind: MatchKind,
+}
+
+/// What happens when a workflow uses a trigger id that another loaded workflow already has.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub enum CollisionPolicy {
+    // the later workflow is not added
+    #[default]
+    Reject,
+    // the later workflow takes over the trigger, a warning is logged
+    LastWins,
+    // all of them keep it, firing it lets the user choose
+    Choose,
+}
+
 pub type WorkSender = Sender<Work>;
 pub type WorkReceiver = Receiver<Work>;
 
-pub struct Executor {
-    workflows_pid: HashMap<WorkflowId, WorkflowPid>,
+struct Run {
+    workflow_pid: WorkflowPid,
+    // number of works that are executing or waiting for the frontend
+    works: usize,
+    cancellation: CancellationToken,
+}
+
+/// Bookkeeping shared between the executor and its workers.
+#[derive(Default)]
+struct Runs {
     active_workflows: HashMap<WorkflowPid, Arc<Workflow>>,
+    // number of unfinished runs
     running_workflows: HashMap<WorkflowPid, usize>,
     removing_workflows: HashSet<WorkflowPid>,
-    entries: HashMap<TriggerId, WorkNode>,
-    queue: VecDeque<Work>,
+    runs: HashMap<RunId, Run>,
+    // latest run of each workflow instance still at its prompt,
+    // superseded by the next keyword input
+    latest_runs: HashMap<WorkflowPid, RunId>,
+}
+
+impl Runs {
+    /// Registers a new run, `supersede` cancels the latest run of the instance first.
+    fn start_run(
+        &mut self,
+        workflow_pid: WorkflowPid,
+        supersede: bool,
+    ) -> (RunId, CancellationToken) {
+        if supersede {
+            if let Some(run_id) = self.latest_runs.get(&workflow_pid).copied() {
+                self.cancel_run(run_id);
+            }
+        }
+
+        let run_id = Uuid::new_v4();
+        let cancellation = CancellationToken::default();
+        self.runs.insert(
+            run_id,
+            Run {
+                workflow_pid,
+                works: 0,
+                cancellation: cancellation.clone(),
+            },
+        );
+        self.latest_runs.insert(workflow_pid, run_id);
+        self.running_workflows
+            .entry(workflow_pid)
+            .and_modify(|e| *e += 1);
+
+        (run_id, cancellation)
+    }
+
+    fn increase_work(&mut self, run_id: RunId) {
+        if let Some(run) = self.runs.get_mut(&run_id) {
+            run.works += 1;
+        }
+    }
+
+    fn decrease_work(&mut self, run_id: RunId) {
+        if let Some(run) = self.runs.get_mut(&run_id) {
+            run.works = run.works.saturating_sub(1);
+            if run.works == 0 {
+                self.finish_run(run_id);
+            }
+        }
+    }
+
+    /// The run went past its prompt and goes on acting, a new keyword input no longer cancels it.
+    fn leave_prompt(&mut self, run_id: RunId) {
+        if let Some(run) = self.runs.get(&run_id) {
+            if self.latest_runs.get(&run.workflow_pid) == Some(&run_id) {
+                self.latest_runs.remove(&run.workflow_pid);
+            }
+        }
+    }
+
+    fn cancel_run(&mut self, run_id: RunId) -> bool {
+        match self.runs.get(&run_id) {
+            Some(run) => {
+                run.cancellation.cancel();
+                self.finish_run(run_id);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Cancelled runs finish at once, their remaining works are ignored.
+    fn finish_run(&mut self, run_id: RunId) {
+        if let Some(run) = self.runs.remove(&run_id) {
+            let workflow_pid = run.workflow_pid;
+            if self.latest_runs.get(&workflow_pid) == Some(&run_id) {
+                self.latest_runs.remove(&workflow_pid);
+            }
+            self.running_workflows
+                .entry(workflow_pid)
+                .and_modify(|e| *e = e.saturating_sub(1));
+
+            if self.removing_workflows.contains(&workflow_pid) {
+                self.try_remove_workflow(workflow_pid);
+            }
+        }
+    }
+
+    fn cancellation(&self, run_id: RunId) -> Option<CancellationToken> {
+        self.runs.get(&run_id).map(|run| run.cancellation.clone())
+    }
+
+    fn try_remove_workflow(&mut self, workflow_pid: WorkflowPid) {
+        let running = self.running_workflows.get(&workflow_pid).copied();
+        if running.unwrap_or_default() == 0 {
+            self.active_workflows.remove(&workflow_pid);
+            self.running_workflows.remove(&workflow_pid);
+            self.removing_workflows.remove(&workflow_pid);
+            self.latest_runs.remove(&workflow_pid);
+        }
+    }
+}
+
+/// Where nodes are executed.
+enum Driver {
+    // blocking nodes on worker threads
+    Pool(WorkerPool),
+    // async nodes are awaited, the others run on blocking threads of the runtime
+    Runtime(tokio::runtime::Handle),
+}
+
+fn now() -> i64 {
+    chrono::Utc::now().timestamp()
+}
+
+fn execute_node(
+    workflow: &Workflow,
+    node_id: NodeId,
+    params: Params,
+) -> crate::result::Result<Params> {
+    if params.is_cancelled() {
+        return Err(crate::Error::Cancelled);
+    }
+    match workflow.get_node(&node_id) {
+        Some(node) => node.node.execute(params),
+        None => Err(crate::Error::NodeNotFound(node_id)),
+    }
+}
+
+async fn execute_node_async(
+    workflow: Arc<Workflow>,
+    node_id: NodeId,
+    params: Params,
+) -> crate::result::Result<Params> {
+    if params.is_cancelled() {
+        return Err(crate::Error::Cancelled);
+    }
+    if let Some(node) = workflow
+        .get_node(&node_id)
+        .and_then(|node| node.node.as_async())
+    {
+        return node.execute_async(params).await;
+    }
+    tokio::task::spawn_blocking(move || execute_node(&workflow, node_id, params))
+        .await
+        .map_err(|err| crate::Error::new(err.to_string()))?
+}
+
+pub struct Executor {
+    workflows_pid: HashMap<WorkflowId, WorkflowPid>,
+    runs: Arc<Mutex<Runs>>,
+    // owners of each trigger in the order they were added
+    entries: HashMap<TriggerId, Vec<WorkNode>>,
+    collision_policy: CollisionPolicy,
+    frecency: Arc<Mutex<Frecency>>,
+    hotkeys: HotkeyRegistry,
+    scheduler: Scheduler,
+    watcher: FileWatcher,
+    driver: Driver,
     work_sender: WorkSender,
     work_receiver: WorkReceiver,
 }
 
 impl Executor {
     pub fn default() -> Self {
+        Self::with_driver(Driver::Pool(WorkerPool::default()))
+    }
+
+    pub fn with_workers(workers: usize) -> Self {
+        Self::with_driver(Driver::Pool(WorkerPool::new(workers)))
+    }
+
+    /// Executes nodes on a tokio runtime instead of the worker pool,
+    /// so async nodes don't block a thread while waiting.
+    pub fn with_runtime(handle: tokio::runtime::Handle) -> Self {
+        Self::with_driver(Driver::Runtime(handle))
+    }
+
+    fn with_driver(driver: Driver) -> Self {
         let (work_sender, work_receiver): (WorkSender, WorkReceiver) =
             crossbeam_channel::unbounded();
         Self {
             workflows_pid: HashMap::new(),
-            active_workflows: HashMap::new(),
-            running_workflows: HashMap::new(),
-            removing_workflows: HashSet::new(),
+            runs: Arc::new(Mutex::new(Runs::default())),
             entries: HashMap::new(),
-            queue: VecDeque::new(),
+            collision_policy: CollisionPolicy::default(),
+            frecency: Arc::new(Mutex::new(Frecency::default())),
+            hotkeys: HotkeyRegistry::default(),
+            scheduler: Scheduler::default(),
+            watcher: FileWatcher::default(),
+            driver,
             work_sender,
             work_receiver,
         }
@@ -82,156 +322,629 @@ impl Executor {
         &self.work_receiver
     }
 
-    pub fn add_workflow(&mut self, workflow: Arc<Workflow>) {
+    pub fn hotkeys(&self) -> &HotkeyRegistry {
+        &self.hotkeys
+    }
+
+    pub fn hotkey_receiver(&self) -> &HotkeyReceiver {
+        self.hotkeys.receiver()
+    }
+
+    pub fn scheduler(&self) -> &Scheduler {
+        &self.scheduler
+    }
+
+    pub fn schedule_receiver(&self) -> &ScheduleReceiver {
+        self.scheduler.receiver()
+    }
+
+    pub fn file_watch_receiver(&self) -> &FileWatchReceiver {
+        self.watcher.receiver()
+    }
+
+    pub fn collision_policy(&self) -> CollisionPolicy {
+        self.collision_policy
+    }
+
+    pub fn set_collision_policy(&mut self, collision_policy: CollisionPolicy) {
+        self.collision_policy = collision_policy;
+    }
+
+    /// Snapshot of the usage data, for persisting it.
+    pub fn frecency(&self) -> Frecency {
+        self.frecency.lock().unwrap().clone()
+    }
+
+    pub fn set_frecency(&mut self, frecency: Frecency) {
+        *self.frecency.lock().unwrap() = frecency;
+    }
+
+    /// Forgets which keywords and options of the workflow were picked.
+    pub fn reset_frecency(&self, workflow_id: WorkflowId) -> bool {
+        self.frecency.lock().unwrap().reset_workflow(workflow_id)
+    }
+
+    /// Records that the keyword was picked while `query` was typed.
+    pub fn record_keyword(&self, trigger_id: &str, query: &str) -> bool {
+        match self
+            .entries
+            .get(trigger_id)
+            .and_then(|owners| owners.last())
+        {
+            Some(work_node) => {
+                self.frecency.lock().unwrap().record(
+                    work_node.workflow_id,
+                    PickScope::Trigger,
+                    query,
+                    trigger_id,
+                    now(),
+                );
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Workflow ids owning the trigger, the last one wins unless the policy is `Choose`.
+    pub fn trigger_owners(&self, trigger_id: &str) -> Vec<WorkflowId> {
+        self.entries
+            .get(trigger_id)
+            .into_iter()
+            .flatten()
+            .map(|work_node| work_node.workflow_id)
+            .collect()
+    }
+
+    pub fn add_workflow(&mut self, workflow: Arc<Workflow>) -> crate::result::Result<()> {
         let workflow_pid = Uuid::new_v4();
         let workflow_id = workflow.id;
 
+        // check trigger collisions before adding anything
+        for trigger_id in workflow.get_triggers().keys() {
+            let owner = self
+                .trigger_owners(trigger_id)
+                .into_iter()
+                .find(|owner| *owner != workflow_id);
+            if let Some(owner) = owner {
+                match self.collision_policy {
+                    CollisionPolicy::Reject => {
+                        return Err(crate::Error::TriggerCollision {
+                            trigger_id: trigger_id.clone(),
+                            workflow_id: owner,
+                        })
+                    }
+                    CollisionPolicy::LastWins => {
+                        tracing::warn!(trigger_id, %workflow_id, %owner, "trigger taken over");
+                    }
+                    CollisionPolicy::Choose => {}
+                }
+            }
+        }
+
         // add workflow
         self.workflows_pid.insert(workflow.id, workflow_pid);
-        self.active_workflows.insert(workflow_pid, workflow.clone());
-        self.running_workflows.insert(workflow_pid, 0);
+        let mut runs = self.runs.lock().unwrap();
+        runs.active_workflows.insert(workflow_pid, workflow.clone());
+        runs.running_workflows.insert(workflow_pid, 0);
+        drop(runs);
 
         // add triggers
         workflow.get_triggers().iter().for_each(|trigger| {
-            self.entries.insert(
-                trigger.0.to_string(),
-                WorkNode {
+            self.entries
+                .entry(trigger.0.to_string())
+                .or_default()
+                .push(WorkNode {
+                    // every trigger starts a new run
+                    run_id: RunId::nil(),
                     workflow_id,
                     workflow_pid,
                     node_id: *trigger.1,
-                },
-            );
+                });
         });
+
+        // add shortcuts
+        self.hotkeys.add_workflow(&workflow);
+
+        // add schedules
+        self.scheduler.add_workflow(&workflow);
+
+        // add file watches
+        self.watcher.add_workflow(&workflow);
+
+        Ok(())
     }
 
+    /// Adds all workflows, the ones that can't be added are logged and skipped.
     pub fn add_workflows(&mut self, workflows: Vec<Arc<Workflow>>) {
-        workflows.into_iter().for_each(|wf| self.add_workflow(wf));
+        for workflow in workflows {
+            let workflow_id = workflow.id;
+            if let Err(err) = self.add_workflow(workflow) {
+                tracing::error!(%workflow_id, %err, "add workflow failed");
+            }
+        }
     }
 
-    pub fn remove_workflow(&mut self, workflow_id: WorkflowId) {
-        let workflow_pid = self.workflows_pid.get(&workflow_id).unwrap().to_owned();
+    /// Loaded workflows, excluding the ones being removed.
+    pub fn workflows(&self) -> Vec<Arc<Workflow>> {
+        let runs = self.runs.lock().unwrap();
+        self.workflows_pid
+            .values()
+            .filter_map(|workflow_pid| runs.active_workflows.get(workflow_pid).cloned())
+            .collect()
+    }
 
-        let workflow = self.active_workflows.get(&workflow_pid).unwrap();
-        self.removing_workflows.insert(workflow_id);
+    pub fn has_workflow(&self, workflow_id: WorkflowId) -> bool {
+        self.workflows_pid.contains_key(&workflow_id)
+    }
 
-        // remove related entries
+    pub fn remove_workflow(&mut self, workflow_id: WorkflowId) -> crate::result::Result<()> {
+        let workflow_pid = *self
+            .workflows_pid
+            .get(&workflow_id)
+            .ok_or(crate::Error::WorkflowNotFound(workflow_id))?;
+
+        let workflow = self
+            .active_workflow(workflow_pid)
+            .ok_or(crate::Error::WorkflowNotFound(workflow_id))?;
+        self.runs
+            .lock()
+            .unwrap()
+            .removing_workflows
+            .insert(workflow_pid);
+
+        // remove own entries, remember the other owners of the same triggers
+        let mut other_owners = HashSet::new();
         workflow.get_triggers().iter().for_each(|trigger| {
-            self.entries.remove(trigger.0);
+            if let Some(owners) = self.entries.get_mut(trigger.0) {
+                owners.retain(|work_node| work_node.workflow_id != workflow_id);
+                other_owners.extend(owners.iter().map(|work_node| work_node.workflow_pid));
+                if owners.is_empty() {
+                    self.entries.remove(trigger.0);
+                }
+            }
         });
 
+        // remove shortcuts
+        self.hotkeys.remove_workflow(workflow_id);
+
+        // remove schedules
+        self.scheduler.remove_workflow(workflow_id);
+
+        // remove file watches
+        self.watcher.remove_workflow(workflow_id);
+
+        // shared shortcuts were registered once, hand them back to the remaining owners
+        for workflow_pid in other_owners {
+            if let Some(other) = self.active_workflow(workflow_pid) {
+                self.hotkeys.add_workflow(&other);
+            }
+        }
+
         // remove workflow pid
         self.workflows_pid.remove(&workflow_id);
 
         self.try_remove_workflow(workflow_pid);
+
+        Ok(())
     }
 
     pub fn try_remove_workflow(&mut self, workflow_pid: WorkflowPid) {
-        if self.running_workflows.get(&workflow_pid).unwrap() == &0 {
-            self.active_workflows.remove(&workflow_pid);
-            self.removing_workflows.remove(&workflow_pid);
-        }
+        self.runs.lock().unwrap().try_remove_workflow(workflow_pid);
+    }
+
+    /// Number of unfinished runs of the workflow instance.
+    pub fn running_runs(&self, workflow_pid: WorkflowPid) -> usize {
+        let runs = self.runs.lock().unwrap();
+        runs.running_workflows
+            .get(&workflow_pid)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Number of works of the workflow instance that are executing or waiting for the frontend.
+    pub fn running_works(&self, workflow_pid: WorkflowPid) -> usize {
+        let runs = self.runs.lock().unwrap();
+        runs.runs
+            .values()
+            .filter(|run| run.workflow_pid == workflow_pid)
+            .map(|run| run.works)
+            .sum()
+    }
+
+    fn active_workflow(&self, workflow_pid: WorkflowPid) -> Option<Arc<Workflow>> {
+        let runs = self.runs.lock().unwrap();
+        runs.active_workflows.get(&workflow_pid).cloned()
+    }
+
+    /// Cancels the run, returns whether it was still running.
+    ///
+    /// Its pending nodes are skipped and their results never reach `receiver()`.
+    pub fn cancel(&self, run_id: RunId) -> bool {
+        self.runs.lock().unwrap().cancel_run(run_id)
     }
 
     pub fn has_trigger(&self, trigger_id: &str) -> bool {
         self.entries.contains_key(trigger_id)
     }
 
+    /// Keyword triggers matching a partial keyword, best matches first.
+    ///
+    /// Keywords are matched by prefix, then fuzzily (pinyin included), then by their titles.
+    /// Matches of the same kind are ranked by their match score, picks only reorder similar ones.
+    pub fn match_keywords(&self, input: &str) -> Vec<TriggerCandidate> {
+        let input = input.trim();
+        if input.is_empty() {
+            return Vec::new();
+        }
+        let lowercase_input = input.to_lowercase();
+        let matcher = FuzzyMatcher::default();
+
+        let candidates = self
+            .entries
+            .iter()
+            .filter_map(|(trigger_id, owners)| {
+                // the owner `trigger` would start
+                let work_node = owners.last()?;
+                let workflow = self.active_workflow(work_node.workflow_pid)?;
+                let node = workflow.get_node(&work_node.node_id)?;
+                let trigger = node.node.as_any().downcast_ref::<Trigger>()?;
+                if !matches!(trigger, Trigger::Keyword { .. }) {
+                    return None;
+                }
+
+                let keyword = trigger_id.to_lowercase();
+                let (kind, match_score) = if keyword == lowercase_input {
+                    (MatchKind::Exact, 0)
+                } else if keyword.starts_with(&lowercase_input) {
+                    (MatchKind::Prefix, 0)
+                } else if let Some(found) = matcher.find(trigger_id, input) {
+                    (MatchKind::Fuzzy, found.score)
+                } else if let Some(found) = matcher.find(trigger.title(), input) {
+                    (MatchKind::Title, found.score)
+                } else {
+                    return None;
+                };
+
+                let candidate = TriggerCandidate {
+                    trigger_id: trigger_id.clone(),
+                    title: trigger.title().to_string(),
+                    workflow_id: work_node.workflow_id,
+                    workflow_title: workflow.title.clone(),
+                    kind,
+                };
+                Some((match_score, candidate))
+            })
+            .collect::<Vec<_>>();
+
+        // better matches first, the most used first among similar matches,
+        // then shorter keywords as they are closer to the input
+        let frecency = self.frecency.lock().unwrap();
+        let now = now();
+        let mut scored = candidates
+            .into_iter()
+            .map(|(match_score, candidate)| {
+                let score = frecency.score(
+                    candidate.workflow_id,
+                    PickScope::Trigger,
+                    input,
+                    &candidate.trigger_id,
+                    now,
+                );
+                (score, match_score, candidate)
+            })
+            .collect::<Vec<_>>();
+        scored.sort_by(|(a_score, a_match, a), (b_score, b_match, b)| {
+            a.kind
+                .cmp(&b.kind)
+                .then((b_match / MATCH_SCORE_BUCKET).cmp(&(a_match / MATCH_SCORE_BUCKET)))
+                .then(b_score.total_cmp(a_score))
+                .then(b_match.cmp(a_match))
+                .then((a.trigger_id.len(), &a.trigger_id).cmp(&(b.trigger_id.len(), &b.trigger_id)))
+        });
+        scored
+            .into_iter()
+            .map(|(_, _, candidate)| candidate)
+            .collect()
+    }
+
+    /// Starts the workflow bound to `trigger_id`, returns whether a trigger matched.
+    ///
+    /// Nodes run on the driver, their results arrive at `receiver()`.
+    /// A trigger shared under `CollisionPolicy::Choose` sends a chooser work instead.
     pub fn trigger<T: Serialize>(
-        &mut self,
+        &self,
         trigger_id: &str,
         params: Option<T>,
     ) -> crate::result::Result<bool> {
-        if let Some(work_node) = self.entries.get(trigger_id) {
-            let work = Work {
-                work_node: work_node.clone(),
-                params: Params::new(match params {
-                    Some(params) => to_value(params)?,
-                    None => Value::Null,
-                }),
-            };
-            self.queue.push_back(work);
-            self.execute_works()?;
-            Ok(true)
-        } else {
-            Ok(false)
+        let owners = match self.entries.get(trigger_id) {
+            Some(owners) => owners,
+            None => return Ok(false),
+        };
+        let value = match params {
+            Some(params) => to_value(params)?,
+            None => Value::Null,
+        };
+
+        match (owners.as_slice(), self.collision_policy) {
+            ([], _) => Ok(false),
+            ([_, _, ..], CollisionPolicy::Choose) => {
+                self.choose(trigger_id, owners, value);
+                Ok(true)
+            }
+            ([.., work_node], _) => {
+                self.start(trigger_id, work_node, value)?;
+                Ok(true)
+            }
         }
     }
 
-    fn execute_works(&mut self) -> crate::result::Result<()> {
-        while let Some(work) = self.queue.pop_front() {
-            let Work { work_node, params } = work;
-            let workflow_pid = &work_node.workflow_pid;
-            let node_id = &work_node.node_id;
-            let workflow = self.active_workflows.get(workflow_pid).unwrap();
-            let node = workflow.get_node(node_id).unwrap();
-
-            let params = node.node.execute(params)?;
-
-            let work = Work { work_node, params };
-
-            self.send_work(work);
-        }
-        Ok(())
+    /// Starts the workflow from one of its trigger nodes, returns whether the trigger exists.
+    ///
+    /// Used for triggers that don't fire by id, such as the ones sent by the `Scheduler`
+    /// and the `FileWatcher`.
+    pub fn trigger_node<T: Serialize>(
+        &self,
+        workflow_id: WorkflowId,
+        node_id: NodeId,
+        params: Option<T>,
+    ) -> crate::result::Result<bool> {
+        let workflow_pid = match self.workflows_pid.get(&workflow_id) {
+            Some(workflow_pid) => *workflow_pid,
+            None => return Ok(false),
+        };
+        let trigger_id = match self
+            .active_workflow(workflow_pid)
+            .as_ref()
+            .and_then(|workflow| workflow.get_node(&node_id))
+            .and_then(|node| node.node.as_any().downcast_ref::<Trigger>())
+        {
+            Some(trigger) => trigger.id(),
+            None => return Ok(false),
+        };
+        let value = match params {
+            Some(params) => to_value(params)?,
+            None => Value::Null,
+        };
+
+        let work_node = WorkNode {
+            run_id: RunId::nil(),
+            workflow_id,
+            workflow_pid,
+            node_id,
+        };
+        self.start(&trigger_id, &work_node, value)?;
+        Ok(true)
     }
 
-    fn send_work(&mut self, work: Work) {
-        let workflow_pid = work.workflow_pid();
-
-        self.work_sender.send(work).unwrap();
-
-        self.increase_workflow(workflow_pid);
+    /// Sends a work listing the owners of the trigger, see `Work::is_chooser`.
+    fn choose(&self, trigger_id: &str, owners: &[WorkNode], value: Value) {
+        let options = owners
+            .iter()
+            .filter_map(|work_node| {
+                let workflow = self.active_workflow(work_node.workflow_pid)?;
+                let title = workflow
+                    .get_node(&work_node.node_id)
+                    .and_then(|node| node.node.as_any().downcast_ref::<Trigger>())
+                    .map(|trigger| trigger.title().to_string())
+                    .unwrap_or_default();
+                Some(ListOption {
+                    title: workflow.title.clone(),
+                    value: serde_json::json!({
+                        "trigger_id": trigger_id,
+                        "workflow_id": work_node.workflow_id,
+                    }),
+                    description: Some(title),
+                    mark: None,
+                    highlights: None,
+                })
+            })
+            .collect();
+
+        let mut params = Params::new(value);
+        params.set_prompt(Prompt::Select {
+            text: trigger_id.to_string(),
+            options,
+            default: Some(0),
+        });
+        let work = Work {
+            work_node: WorkNode {
+                run_id: RunId::nil(),
+                workflow_pid: WorkflowPid::nil(),
+                workflow_id: WorkflowId::nil(),
+                node_id: NodeId::nil(),
+            },
+            params,
+        };
+        self.work_sender.send(work).ok();
     }
 
-    fn increase_workflow(&mut self, workflow_pid: WorkflowPid) {
-        self.running_workflows
-            .entry(workflow_pid)
-            .and_modify(|e| *e += 1);
+    /// Starts the workflow chosen from a chooser work.
+    fn receive_choice(&self, work: Work, value: Option<Value>) -> crate::result::Result<()> {
+        #[derive(Deserialize)]
+        struct Choice {
+            trigger_id: TriggerId,
+            workflow_id: WorkflowId,
+        }
+        let choice: Choice = serde_json::from_value(value.unwrap_or_default())?;
+        let work_node = self
+            .entries
+            .get(&choice.trigger_id)
+            .into_iter()
+            .flatten()
+            .find(|work_node| work_node.workflow_id == choice.workflow_id)
+            .ok_or(crate::Error::WorkflowNotFound(choice.workflow_id))?;
+
+        self.start(
+            &choice.trigger_id,
+            work_node,
+            work.params.get_value().clone(),
+        )
     }
 
-    fn decrease_workflow(&mut self, workflow_pid: WorkflowPid) {
-        self.running_workflows
-            .entry(workflow_pid)
-            .and_modify(|e| *e -= 1);
+    fn start(
+        &self,
+        trigger_id: &str,
+        work_node: &WorkNode,
+        value: Value,
+    ) -> crate::result::Result<()> {
+        let workflow = self
+            .active_workflow(work_node.workflow_pid)
+            .ok_or(crate::Error::WorkflowNotFound(work_node.workflow_id))?;
+        let mut params = Params::new(value);
+
+        // a new keyword input supersedes the search still in flight
+        let supersede = workflow
+            .get_keywords()
+            .iter()
+            .any(|keyword| keyword.id() == trigger_id);
+        let (run_id, cancellation) = self
+            .runs
+            .lock()
+            .unwrap()
+            .start_run(work_node.workflow_pid, supersede);
+        params.set_cancellation(cancellation);
+        tracing::debug!(%run_id, trigger_id, "run started");
+
+        let work = Work {
+            work_node: WorkNode {
+                run_id,
+                ..work_node.clone()
+            },
+            params,
+        };
+        self.dispatch(workflow, work);
+        Ok(())
+    }
 
-        if self.removing_workflows.get(&workflow_pid).is_some() {
-            self.try_remove_workflow(workflow_pid);
+    /// Executes the node of `work` on the driver and sends the result to the frontend.
+    fn dispatch(&self, workflow: Arc<Workflow>, work: Work) {
+        let run_id = work.run_id();
+        self.runs.lock().unwrap().increase_work(run_id);
+
+        let runs = self.runs.clone();
+        let frecency = self.frecency.clone();
+        let work_sender = self.work_sender.clone();
+        let Work { work_node, params } = work;
+        let node_id = work_node.node_id;
+        let cancellation = params.cancellation().clone();
+        let finish = move |result: crate::result::Result<Params>| match result {
+            // still running until the frontend sends it back
+            Ok(mut params) if !cancellation.is_cancelled() => {
+                frecency.lock().unwrap().rank_prompt(
+                    work_node.workflow_id,
+                    work_node.node_id,
+                    &mut params,
+                    now(),
+                );
+                work_sender.send(Work { work_node, params }).ok();
+            }
+            // the run ends, the frontend shows the error as the only option
+            Err(err) if !cancellation.is_cancelled() && !matches!(err, crate::Error::Cancelled) => {
+                tracing::error!(%run_id, node_id = %work_node.node_id, %err, "execute node failed");
+                runs.lock().unwrap().decrease_work(run_id);
+                let mut params = Params::new(Value::Null);
+                params.set_prompt(Prompt::Select {
+                    text: "error".to_string(),
+                    options: vec![ListOption::error(err.to_string())],
+                    default: None,
+                });
+                work_sender.send(Work { work_node, params }).ok();
+            }
+            // cancelled runs are dropped silently
+            _ => {
+                tracing::debug!(%run_id, node_id = %work_node.node_id, "node cancelled");
+                runs.lock().unwrap().decrease_work(run_id);
+            }
+        };
+
+        match &self.driver {
+            Driver::Pool(pool) => pool.execute(move || {
+                finish(execute_node(&workflow, node_id, params));
+            }),
+            Driver::Runtime(handle) => {
+                handle.spawn(async move {
+                    finish(execute_node_async(workflow, node_id, params).await);
+                });
+            }
         }
     }
 
     pub fn receive_work(
-        &mut self,
+        &self,
         mut work: Work,
         value: Option<Value>,
+        modifiers: &[Modifier],
     ) -> crate::result::Result<()> {
+        if work.is_chooser() {
+            return self.receive_choice(work, value);
+        }
+
+        let run_id = work.run_id();
         let workflow_pid = work.workflow_pid();
         let node_id = work.node_id();
         let workflow_id = work.workflow_id();
 
+        // the value the node got is what the user typed when picking
+        let query = utils::value_to_string(work.params.get_value());
+        let picked = value.as_ref().filter(|value| !value.is_null()).cloned();
+        // answering a prompt moves the run on, whatever the value
+        let past_prompt = work.params.prompt.is_some() || picked.is_some();
         if let Some(value) = value {
             work.params.set_value(value);
         }
-        let params = work.params;
-
-        let workflow = self.active_workflows.get(&workflow_pid).unwrap();
-        let next_nodes = workflow.next_nodes(&node_id, &params)?.into_iter();
-
-        for next_node in next_nodes {
-            let next_work = Work {
-                work_node: WorkNode {
-                    workflow_id,
-                    workflow_pid,
-                    node_id: next_node.id,
-                },
-                params: params.clone(),
-            };
-            self.queue.push_back(next_work);
+        let mut params = work.params;
+
+        // works sent back by the frontend lose their token,
+        // those of finished or cancelled runs are stale
+        let mut runs = self.runs.lock().unwrap();
+        let cancellation = match runs.cancellation(run_id) {
+            Some(cancellation) => cancellation,
+            None => return Ok(()),
+        };
+        if past_prompt {
+            runs.leave_prompt(run_id);
+        }
+        drop(runs);
+        params.set_cancellation(cancellation);
+
+        if let Some(picked) = picked {
+            self.frecency.lock().unwrap().record(
+                workflow_id,
+                PickScope::Node(node_id),
+                &query,
+                &frecency::option_item(&picked),
+                now(),
+            );
         }
 
-        self.execute_works()?;
+        let workflow = match self.active_workflow(workflow_pid) {
+            Some(workflow) => workflow,
+            None => {
+                self.runs.lock().unwrap().decrease_work(run_id);
+                return Err(crate::Error::WorkflowNotFound(workflow_id));
+            }
+        };
+        let next_nodes = workflow.next_nodes(&node_id, &params, modifiers);
+
+        // dispatch before decreasing, so the workflow can't be removed in between
+        if let Ok(next_nodes) = &next_nodes {
+            for next_node in next_nodes {
+                let next_work = Work {
+                    work_node: WorkNode {
+                        run_id,
+                        workflow_id,
+                        workflow_pid,
+                        node_id: next_node.id,
+                    },
+                    params: params.clone(),
+                };
+                self.dispatch(workflow.clone(), next_work);
+            }
+        }
 
-        self.decrease_workflow(workflow_pid);
+        self.runs.lock().unwrap().decrease_work(run_id);
 
-        Ok(())
+        next_nodes.map(|_| ())
     }
 
     pub fn execute_workflow_json<T: Serialize>(
@@ -241,7 +954,7 @@ impl Executor {
         params: Option<T>,
     ) -> crate::result::Result<bool> {
         let workflow = WorkflowBuilder::default().json(json).build()?;
-        self.add_workflow(Arc::new(workflow));
+        self.add_workflow(Arc::new(workflow))?;
 
         self.trigger(trigger_id, params)
     }
@@ -0,0 +1,160 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use uuid::Uuid;
+
+use crate::nodes::list_filter::ListOption;
+use crate::params::Params;
+use crate::prompt::Prompt;
+
+// oldest picks are dropped beyond this, per workflow
+const MAX_PICKS: usize = 500;
+
+const HOUR: i64 = 60 * 60;
+const DAY: i64 = 24 * HOUR;
+
+/// What a pick was made from.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(tag = "type", content = "id")]
+pub enum PickScope {
+    // a keyword candidate
+    Trigger,
+    // an option listed by the node
+    Node(Uuid),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Pick {
+    scope: PickScope,
+    // what the user had typed
+    query: String,
+    // trigger id, or the json of the option value
+    item: String,
+    count: u32,
+    // unix timestamp in seconds
+    last_used: i64,
+}
+
+impl Pick {
+    /// Frequency weighted by recency.
+    fn score(&self, now: i64) -> f64 {
+        let age = now - self.last_used;
+        let weight = if age < 4 * HOUR {
+            1.0
+        } else if age < DAY {
+            0.7
+        } else if age < 7 * DAY {
+            0.5
+        } else if age < 30 * DAY {
+            0.3
+        } else {
+            0.1
+        };
+        self.count as f64 * weight
+    }
+
+    /// Picks made while typing a prefix of the query, or a longer query, count for it.
+    fn matches_query(&self, query: &str) -> bool {
+        let query = query.to_lowercase();
+        self.query.starts_with(&query) || query.starts_with(&self.query)
+    }
+}
+
+/// Which keywords and list options the user picks, used to rank them.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Frecency {
+    // key is workflow id
+    #[serde(default)]
+    workflows: HashMap<Uuid, Vec<Pick>>,
+}
+
+impl Frecency {
+    pub fn record(
+        &mut self,
+        workflow_id: Uuid,
+        scope: PickScope,
+        query: &str,
+        item: &str,
+        now: i64,
+    ) {
+        let query = query.to_lowercase();
+        let picks = self.workflows.entry(workflow_id).or_default();
+        match picks
+            .iter_mut()
+            .find(|pick| pick.scope == scope && pick.query == query && pick.item == item)
+        {
+            Some(pick) => {
+                pick.count += 1;
+                pick.last_used = now;
+            }
+            None => picks.push(Pick {
+                scope,
+                query,
+                item: item.to_string(),
+                count: 1,
+                last_used: now,
+            }),
+        }
+
+        if picks.len() > MAX_PICKS {
+            picks.sort_by_key(|pick| std::cmp::Reverse(pick.last_used));
+            picks.truncate(MAX_PICKS);
+        }
+    }
+
+    pub fn score(
+        &self,
+        workflow_id: Uuid,
+        scope: PickScope,
+        query: &str,
+        item: &str,
+        now: i64,
+    ) -> f64 {
+        self.workflows
+            .get(&workflow_id)
+            .into_iter()
+            .flatten()
+            .filter(|pick| pick.scope == scope && pick.item == item && pick.matches_query(query))
+            .map(|pick| pick.score(now))
+            .sum()
+    }
+
+    /// Forgets the picks of the workflow, returns whether there were any.
+    pub fn reset_workflow(&mut self, workflow_id: Uuid) -> bool {
+        self.workflows.remove(&workflow_id).is_some()
+    }
+
+    /// Moves the most used options of a select prompt to the top.
+    ///
+    /// Lists starting with a hint or an error are left alone, trailing hints stay last.
+    pub fn rank_prompt(&self, workflow_id: Uuid, node_id: Uuid, params: &mut Params, now: i64) {
+        if !self.workflows.contains_key(&workflow_id) {
+            return;
+        }
+        let query = crate::utils::value_to_string(params.get_value());
+        if let Some(Prompt::Select { options, .. } | Prompt::FuzzySelect { options, .. }) =
+            &mut params.prompt
+        {
+            if !matches!(options.first(), Some(option) if option.mark.is_none()) {
+                return;
+            }
+            let score = |option: &ListOption| {
+                let item = option_item(&option.value);
+                self.score(workflow_id, PickScope::Node(node_id), &query, &item, now)
+            };
+            let mut scored = options
+                .drain(..)
+                .map(|option| (score(&option), option))
+                .collect::<Vec<_>>();
+            // stable, so unused options keep their order
+            scored.sort_by(|a, b| b.0.total_cmp(&a.0));
+            options.extend(scored.into_iter().map(|(_, option)| option));
+        }
+    }
+}
+
+/// How a picked option value is stored.
+pub fn option_item(value: &Value) -> String {
+    value.to_string()
+}
@@ -0,0 +1,105 @@
+use std::collections::HashMap;
+
+use crossbeam_channel::{Receiver, Sender};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{nodes::trigger::Trigger, workflow::Workflow};
+
+/// A global shortcut bound to a `Trigger::Shortcut`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Hotkey {
+    // trigger id, e.g. "Shift+Ctrl+K", pass it to `Executor::trigger` when pressed
+    pub id: String,
+    pub keys: Vec<String>,
+    pub title: String,
+    pub workflow_id: Uuid,
+}
+
+impl Hotkey {
+    /// Accelerator string for the OS global-shortcut API.
+    pub fn accelerator(&self) -> &str {
+        &self.id
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", content = "hotkey")]
+pub enum HotkeyEvent {
+    Register(Hotkey),
+    Unregister(Hotkey),
+}
+
+pub type HotkeySender = Sender<HotkeyEvent>;
+pub type HotkeyReceiver = Receiver<HotkeyEvent>;
+
+/// Keeps track of the shortcuts of all loaded workflows.
+///
+/// Every change is sent as a `HotkeyEvent`, frontends apply them to the OS global-shortcut API.
+pub struct HotkeyRegistry {
+    // key is trigger id
+    hotkeys: HashMap<String, Hotkey>,
+    sender: HotkeySender,
+    receiver: HotkeyReceiver,
+}
+
+impl Default for HotkeyRegistry {
+    fn default() -> Self {
+        let (sender, receiver) = crossbeam_channel::unbounded();
+        Self {
+            hotkeys: HashMap::new(),
+            sender,
+            receiver,
+        }
+    }
+}
+
+impl HotkeyRegistry {
+    pub fn receiver(&self) -> &HotkeyReceiver {
+        &self.receiver
+    }
+
+    pub fn hotkeys(&self) -> Vec<&Hotkey> {
+        self.hotkeys.values().collect()
+    }
+
+    pub fn get(&self, id: &str) -> Option<&Hotkey> {
+        self.hotkeys.get(id)
+    }
+
+    pub fn add_workflow(&mut self, workflow: &Workflow) {
+        for trigger in workflow.get_shortcuts() {
+            if let Trigger::Shortcut { keys, title } = trigger {
+                let hotkey = Hotkey {
+                    id: trigger.id(),
+                    keys: keys.clone(),
+                    title: title.clone(),
+                    workflow_id: workflow.id,
+                };
+                if let Some(old) = self.hotkeys.insert(hotkey.id.clone(), hotkey.clone()) {
+                    self.send(HotkeyEvent::Unregister(old));
+                }
+                self.send(HotkeyEvent::Register(hotkey));
+            }
+        }
+    }
+
+    pub fn remove_workflow(&mut self, workflow_id: Uuid) {
+        let ids = self
+            .hotkeys
+            .values()
+            .filter(|hotkey| hotkey.workflow_id == workflow_id)
+            .map(|hotkey| hotkey.id.clone())
+            .collect::<Vec<_>>();
+        for id in ids {
+            if let Some(hotkey) = self.hotkeys.remove(&id) {
+                self.send(HotkeyEvent::Unregister(hotkey));
+            }
+        }
+    }
+
+    fn send(&self, event: HotkeyEvent) {
+        // the registry owns a receiver, so sending never fails
+        self.sender.send(event).ok();
+    }
+}
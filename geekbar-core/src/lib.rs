@@ -1,12 +1,19 @@
+pub mod cancel;
 pub mod error;
 pub mod executor;
+pub mod frecency;
+pub mod hotkey;
 pub mod link;
 pub mod node;
 pub mod nodes;
 pub mod params;
+pub mod pool;
 pub mod prompt;
 pub mod result;
+pub mod scheduler;
 pub mod utils;
+pub mod validator;
+pub mod watcher;
 pub mod workflow;
 
 pub use error::Error;
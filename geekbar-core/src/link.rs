@@ -1,15 +1,48 @@
+use regex::Regex;
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::cmp::Ordering::{self, Equal, Greater, Less};
+use std::collections::HashSet;
 use uuid::Uuid;
 
+use crate::error::Error;
+use crate::params::Params;
+use crate::utils;
+
 #[derive(Serialize, Deserialize, Clone)]
 pub struct Link {
     pub from: Uuid,
     pub to: Uuid,
     pub condition: Option<Condition>,
     pub modifiers: Option<Vec<Modifier>>,
+    // links closing a loop must opt in, otherwise the validator reports a cycle
+    #[serde(default)]
+    pub allow_cycle: bool,
 }
 
-#[derive(Serialize, Deserialize, Clone)]
+impl Link {
+    /// Whether this link should be followed, links without condition always are.
+    pub fn is_satisfied(&self, params: &Params) -> crate::result::Result<bool> {
+        match &self.condition {
+            Some(condition) => condition.evaluate(params),
+            None => Ok(true),
+        }
+    }
+
+    /// Links without modifiers are the default path.
+    pub fn is_default(&self) -> bool {
+        self.modifiers.iter().flatten().next().is_none()
+    }
+
+    /// Whether the held modifiers are exactly the ones bound to this link.
+    pub fn matches_modifiers(&self, modifiers: &[Modifier]) -> bool {
+        let bound = self.modifiers.iter().flatten().collect::<HashSet<_>>();
+        let held = modifiers.iter().collect::<HashSet<_>>();
+        bound == held
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum Modifier {
     Ctrl,
     Alt,
@@ -25,20 +58,126 @@ pub enum Condition {
     Value(ValueCondition),
 }
 
+impl Condition {
+    pub fn evaluate(&self, params: &Params) -> crate::result::Result<bool> {
+        match self {
+            Condition::And(condition) => condition.evaluate(params),
+            Condition::Or(condition) => condition.evaluate(params),
+            Condition::Value(condition) => condition.evaluate(params),
+        }
+    }
+
+    /// Errors of `Match` patterns that can be checked before rendering.
+    pub fn invalid_regexes(&self) -> Vec<String> {
+        match self {
+            Condition::And(AndCondition { conditions })
+            | Condition::Or(OrCondition { conditions }) => conditions
+                .iter()
+                .flat_map(Condition::invalid_regexes)
+                .collect(),
+            Condition::Value(ValueCondition {
+                operator: ValueOperator::Match(Value::String(pattern)),
+                ..
+            }) if !pattern.contains("{{") => match Regex::new(pattern) {
+                Ok(_) => Vec::new(),
+                Err(err) => vec![format!("invalid regex \"{}\": {}", pattern, err)],
+            },
+            Condition::Value(_) => Vec::new(),
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone)]
 pub struct AndCondition {
-    #[allow(dead_code)]
     conditions: Vec<Condition>,
 }
 
+impl AndCondition {
+    pub fn evaluate(&self, params: &Params) -> crate::result::Result<bool> {
+        for condition in &self.conditions {
+            if !condition.evaluate(params)? {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone)]
 pub struct OrCondition {
-    #[allow(dead_code)]
     conditions: Vec<Condition>,
 }
 
+impl OrCondition {
+    pub fn evaluate(&self, params: &Params) -> crate::result::Result<bool> {
+        // An empty group imposes no constraint.
+        if self.conditions.is_empty() {
+            return Ok(true);
+        }
+        for condition in &self.conditions {
+            if condition.evaluate(params)? {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ValueCondition {
+    #[serde(default)]
+    pub subject: ConditionSubject,
+    pub operator: ValueOperator,
+}
+
+impl ValueCondition {
+    pub fn evaluate(&self, params: &Params) -> crate::result::Result<bool> {
+        let value = self.subject.resolve(params);
+        self.operator.test(value, params)
+    }
+}
+
+/// What a value condition compares against.
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(tag = "type")]
+pub enum ConditionSubject {
+    /// The current value.
+    Value {
+        #[serde(default)]
+        path: Option<String>,
+    },
+    /// A context variable set with `Params::set_var`.
+    Var {
+        name: String,
+        #[serde(default)]
+        path: Option<String>,
+    },
+}
+
+impl Default for ConditionSubject {
+    fn default() -> Self {
+        ConditionSubject::Value { path: None }
+    }
+}
+
+impl ConditionSubject {
+    /// Missing variables or paths resolve to `null`.
+    pub fn resolve<'a>(&self, params: &'a Params) -> &'a Value {
+        let (value, path) = match self {
+            ConditionSubject::Value { path } => (Some(params.get_value()), path),
+            ConditionSubject::Var { name, path } => (params.get_var(name), path),
+        };
+        let value = match (value, path) {
+            (Some(value), Some(path)) => utils::json_path(value, path),
+            (value, None) => value,
+            (None, _) => None,
+        };
+        value.unwrap_or(&Value::Null)
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone)]
-pub enum ValueCondition {
+pub enum ValueOperator {
     Eq(serde_json::Value),
     Gt(serde_json::Value),
     Ge(serde_json::Value),
@@ -51,3 +190,108 @@ pub enum ValueCondition {
     NonNull,
     Nullable,
 }
+
+impl ValueOperator {
+    /// Tests the value, operands are rendered as templates first.
+    pub fn test(&self, value: &Value, params: &Params) -> crate::result::Result<bool> {
+        let render = |operand: &Value| params.render_value(operand.clone());
+        let result = match self {
+            ValueOperator::Eq(operand) => loose_eq(value, &render(operand)),
+            ValueOperator::Gt(operand) => {
+                matches!(compare(value, &render(operand)), Some(Greater))
+            }
+            ValueOperator::Ge(operand) => {
+                matches!(compare(value, &render(operand)), Some(Greater | Equal))
+            }
+            ValueOperator::Lt(operand) => matches!(compare(value, &render(operand)), Some(Less)),
+            ValueOperator::Le(operand) => {
+                matches!(compare(value, &render(operand)), Some(Less | Equal))
+            }
+            ValueOperator::Match(operand) => {
+                let pattern = render(operand);
+                let pattern = pattern.as_str().ok_or_else(|| {
+                    Error::ConditionError(format!("Match expects a regex string, got {}", pattern))
+                })?;
+                let regex = Regex::new(pattern)?;
+                regex.is_match(&utils::value_to_string(value))
+            }
+            ValueOperator::Between(operand) => {
+                let range = render(operand);
+                match range.as_array().map(Vec::as_slice) {
+                    Some([min, max]) => {
+                        matches!(compare(value, min), Some(Greater | Equal))
+                            && matches!(compare(value, max), Some(Less | Equal))
+                    }
+                    _ => {
+                        return Err(Error::ConditionError(format!(
+                            "Between expects [min, max], got {}",
+                            range
+                        )))
+                    }
+                }
+            }
+            ValueOperator::In(operand) => match render(operand) {
+                Value::Array(items) => items.iter().any(|item| loose_eq(value, item)),
+                Value::String(s) => s.contains(&utils::value_to_string(value)),
+                Value::Object(o) => o.contains_key(&utils::value_to_string(value)),
+                operand => {
+                    return Err(Error::ConditionError(format!(
+                        "In expects an array, string or object, got {}",
+                        operand
+                    )))
+                }
+            },
+            ValueOperator::Includes(operand) => {
+                let operand = render(operand);
+                match value {
+                    Value::Array(items) => items.iter().any(|item| loose_eq(item, &operand)),
+                    Value::String(s) => s.contains(&utils::value_to_string(&operand)),
+                    Value::Object(o) => o.contains_key(&utils::value_to_string(&operand)),
+                    _ => false,
+                }
+            }
+            ValueOperator::NonNull => !value.is_null(),
+            ValueOperator::Nullable => value.is_null(),
+        };
+        Ok(result)
+    }
+}
+
+fn value_to_number(value: &Value) -> Option<f64> {
+    match value {
+        Value::Number(n) => n.as_f64(),
+        Value::String(s) => s.trim().parse().ok(),
+        _ => None,
+    }
+}
+
+/// Loose equality, numbers equal their numeric string form.
+fn loose_eq(a: &Value, b: &Value) -> bool {
+    if a == b {
+        return true;
+    }
+    match (a, b) {
+        (Value::String(_), Value::String(_)) => false,
+        (Value::String(_) | Value::Number(_), Value::String(_) | Value::Number(_)) => {
+            match (value_to_number(a), value_to_number(b)) {
+                (Some(a), Some(b)) => a == b,
+                _ => false,
+            }
+        }
+        (Value::String(s), Value::Bool(b)) | (Value::Bool(b), Value::String(s)) => {
+            s == &b.to_string()
+        }
+        _ => false,
+    }
+}
+
+/// Compares as numbers when possible, otherwise as strings.
+fn compare(a: &Value, b: &Value) -> Option<Ordering> {
+    match (value_to_number(a), value_to_number(b)) {
+        (Some(a), Some(b)) => a.partial_cmp(&b),
+        _ => match (a, b) {
+            (Value::String(a), Value::String(b)) => Some(a.cmp(b)),
+            _ => None,
+        },
+    }
+}
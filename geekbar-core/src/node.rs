@@ -1,10 +1,21 @@
 use std::any::Any;
 use std::fmt::Debug;
 
+use futures::future::BoxFuture;
+
 use crate::params::Params;
 
 #[typetag::serde(tag = "type", content = "config")]
 pub trait Node: Debug + Send + Sync {
     fn execute(&self, params: Params) -> crate::result::Result<Params>;
     fn as_any(&self) -> &dyn Any;
+    /// Nodes that can await their I/O return `Some(self)`,
+    /// others are run by `execute` on a blocking thread.
+    fn as_async(&self) -> Option<&dyn AsyncNode> {
+        None
+    }
+}
+
+pub trait AsyncNode: Send + Sync {
+    fn execute_async(&self, params: Params) -> BoxFuture<'_, crate::result::Result<Params>>;
 }
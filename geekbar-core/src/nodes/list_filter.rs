@@ -1,13 +1,147 @@
 use super::prelude::*;
-use crate::utils;
+use crate::utils::{self, FuzzyMatcher, Matcher, SubstringMatcher};
+
+const fn _default_pinyin() -> bool {
+    true
+}
 
 #[derive(Serialize, Deserialize, Debug)]
 pub struct ListFilter {
     pub title: Option<String>,
     #[serde(default)]
     pub options: Vec<ListOption>,
+    // build the options from the incoming value instead of `options`
+    #[serde(default)]
+    pub items: Option<ListItems>,
     #[serde(default)]
     pub need_args: NeedArgs,
+    // how options are matched against the input, best matches first
+    #[serde(default)]
+    pub matcher: ListMatcher,
+    // also match chinese by pinyin or its initials, e.g. "bd" for "百度"
+    #[serde(default = "_default_pinyin")]
+    pub pinyin: bool,
+    // shown while args are required but the input is empty, a localized default if unset
+    #[serde(default)]
+    pub placeholder: Option<ListMessage>,
+    // shown when no option matches the input
+    #[serde(default)]
+    pub empty: Option<ListMessage>,
+    // shown after the options, e.g. what picking one does
+    #[serde(default)]
+    pub hint: Option<ListMessage>,
+}
+
+/// A message shown as an option that can't be picked.
+///
+/// Title and description are templates, `{{query}}` is the input and the `locale` helper the
+/// language (the `locale` var or the system one), e.g.
+/// `{{#if (eq (locale) "zh-CN")}}无结果{{else}}No results{{/if}}`.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ListMessage {
+    #[serde(default)]
+    pub title: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub mark: ListOptionMark,
+}
+
+impl ListMessage {
+    fn placeholder(locale: &str) -> Self {
+        let title = if locale.starts_with("zh") {
+            "请输入关键字"
+        } else {
+            "Please input keyword"
+        };
+        Self {
+            title: title.to_string(),
+            description: None,
+            mark: ListOptionMark::Hint,
+        }
+    }
+
+    pub fn render(&self, params: &Params) -> ListOption {
+        ListOption {
+            title: params.render_template(&self.title),
+            value: Value::Null,
+            description: self
+                .description
+                .as_ref()
+                .map(|desc| params.render_template(desc)),
+            mark: Some(self.mark.clone()),
+            highlights: None,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub enum ListMatcher {
+    #[default]
+    Fuzzy,
+    Substring,
+}
+
+impl ListMatcher {
+    pub fn matcher(&self, pinyin: bool) -> Box<dyn Matcher> {
+        match self {
+            ListMatcher::Fuzzy => Box::new(FuzzyMatcher { pinyin }),
+            ListMatcher::Substring => Box::new(SubstringMatcher),
+        }
+    }
+}
+
+/// Options built from an array in the value, e.g. a `Request` response.
+///
+/// The templates are rendered once per item, which is the `item` var (`{{item.name}}`),
+/// with its position as the `index` var.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ListItems {
+    // path to the array, defaults to the value itself
+    #[serde(default)]
+    pub path: Option<String>,
+    #[serde(default)]
+    pub title: String,
+    // defaults to the whole item
+    #[serde(default)]
+    pub value: Value,
+    #[serde(default)]
+    pub description: Option<String>,
+}
+
+impl ListItems {
+    pub fn options(&self, params: &Params) -> crate::result::Result<Vec<ListOption>> {
+        let value = params.get_value();
+        let items = match &self.path {
+            Some(path) => utils::json_path(value, path).unwrap_or(&Value::Null),
+            None => value,
+        };
+        let items = items
+            .as_array()
+            .ok_or_else(|| crate::Error::new(format!("list items is not an array: {}", items)))?;
+
+        let options = items
+            .iter()
+            .enumerate()
+            .map(|(index, item)| {
+                let mut item_params = params.clone();
+                item_params.set_var("item", item.clone());
+                item_params.set_var("index", index.into());
+                let option = ListOption {
+                    title: self.title.clone(),
+                    value: match self.value {
+                        Value::Null => item.clone(),
+                        _ => self.value.clone(),
+                    },
+                    description: self.description.clone(),
+                    mark: None,
+                    highlights: None,
+                };
+                option.render(&item_params)
+            })
+            .collect();
+        Ok(options)
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -16,9 +150,31 @@ pub struct ListOption {
     pub value: Value,
     pub description: Option<String>,
     pub mark: Option<ListOptionMark>,
+    // set when filtering, not part of the config
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub highlights: Option<Highlights>,
+}
+
+/// Matched characters as `[start, end)` char ranges, for frontends to highlight.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct Highlights {
+    #[serde(default)]
+    pub title: Vec<(usize, usize)>,
+    #[serde(default)]
+    pub description: Vec<(usize, usize)>,
 }
 
 impl ListOption {
+    pub fn error(title: impl Into<String>) -> Self {
+        Self {
+            title: title.into(),
+            value: Value::Null,
+            description: None,
+            mark: Some(ListOptionMark::Error),
+            highlights: None,
+        }
+    }
+
     pub fn render(&self, params: &Params) -> Self {
         let title = params.render_template(&self.title);
         let value = params.render_value(self.value.clone());
@@ -31,29 +187,49 @@ impl ListOption {
             value,
             description,
             mark: self.mark.clone(),
+            highlights: self.highlights.clone(),
         }
     }
+
+    /// Matches the title and the description, title matches weigh double.
+    ///
+    /// Returns the score, and sets the highlights if anything was matched.
+    pub fn find(&mut self, matcher: &dyn Matcher, search: &str) -> Option<i64> {
+        let title = matcher.find(&self.title, search);
+        let description = self
+            .description
+            .as_deref()
+            .and_then(|description| matcher.find(description, search));
+        let score = title
+            .iter()
+            .map(|m| m.score * 2)
+            .chain(description.iter().map(|m| m.score))
+            .max()?;
+
+        let highlights = Highlights {
+            title: title.map(|m| m.ranges()).unwrap_or_default(),
+            description: description.map(|m| m.ranges()).unwrap_or_default(),
+        };
+        self.highlights = (highlights != Highlights::default()).then_some(highlights);
+        Some(score)
+    }
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
 pub enum ListOptionMark {
+    #[default]
     Hint,
     Error,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Default)]
 pub enum NeedArgs {
     Always,
     Optional,
+    #[default]
     Never,
 }
 
-impl Default for NeedArgs {
-    fn default() -> Self {
-        NeedArgs::Never
-    }
-}
-
 impl NeedArgs {
     pub fn is_always(&self) -> bool {
         matches!(self, NeedArgs::Always)
@@ -70,44 +246,68 @@ impl NeedArgs {
 impl Node for ListFilter {
     fn execute(&self, mut params: Params) -> crate::result::Result<Params> {
         let search = params.get_value().as_str().unwrap_or_default();
-        let options = match self.need_args {
-            NeedArgs::Never => self
+        let options = || match &self.items {
+            Some(items) => items
+                .options(&params)
+                .unwrap_or_else(|err| vec![ListOption::error(err.to_string())]),
+            None => self
                 .options
                 .iter()
-                .filter(|option| utils::fuzzy_query(&option.title, search))
                 .map(|opt| opt.render(&params))
                 .collect::<Vec<_>>(),
+        };
+        let filtered = || {
+            let matcher = self.matcher.matcher(self.pinyin);
+            let mut ranked = options()
+                .into_iter()
+                .filter_map(|mut option| {
+                    // hints and errors stay on top
+                    if option.mark.is_some() {
+                        return Some((i64::MAX, option));
+                    }
+                    option
+                        .find(matcher.as_ref(), search)
+                        .map(|score| (score, option))
+                })
+                .collect::<Vec<_>>();
+            ranked.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+            ranked
+                .into_iter()
+                .map(|(_, option)| option)
+                .collect::<Vec<_>>()
+        };
+        let options = match self.need_args {
+            NeedArgs::Never => filtered(),
             NeedArgs::Optional => {
                 if search.is_empty() {
-                    self.options
-                        .iter()
-                        .map(|opt| opt.render(&params))
-                        .collect::<Vec<_>>()
+                    options()
                 } else {
-                    self.options
-                        .iter()
-                        .filter(|option| utils::fuzzy_query(&option.title, search))
-                        .map(|opt| opt.render(&params))
-                        .collect::<Vec<_>>()
+                    filtered()
                 }
             }
             NeedArgs::Always => {
                 if search.is_empty() {
-                    vec![ListOption {
-                        // TODO: config by user
-                        title: "Please input keyword".to_string(),
-                        value: Value::Null,
-                        description: None,
-                        mark: Some(ListOptionMark::Hint),
-                    }]
+                    let placeholder = match &self.placeholder {
+                        Some(placeholder) => placeholder.render(&params),
+                        None => ListMessage::placeholder(&params.locale()).render(&params),
+                    };
+                    vec![placeholder]
                 } else {
-                    self.options
-                        .iter()
-                        .map(|opt| opt.render(&params))
-                        .collect::<Vec<_>>()
+                    options()
                 }
             }
         };
+        let is_placeholder = self.need_args.is_always() && search.is_empty();
+        let options = match (&self.empty, &self.hint) {
+            _ if is_placeholder => options,
+            (Some(empty), _) if options.is_empty() => vec![empty.render(&params)],
+            (_, Some(hint)) if !options.is_empty() => {
+                let mut options = options;
+                options.push(hint.render(&params));
+                options
+            }
+            _ => options,
+        };
 
         let prompt = match self.need_args {
             NeedArgs::Always | NeedArgs::Optional => Prompt::Select {
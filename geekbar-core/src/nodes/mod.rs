@@ -5,5 +5,8 @@ pub mod open_url;
 pub mod prelude;
 pub mod request;
 pub mod request_filter;
+pub mod run_script;
+pub mod script_filter;
 pub mod set_vars;
+pub mod transform;
 pub mod trigger;
@@ -1,4 +1,5 @@
-pub use crate::{node::Node, params::Params, prompt::Prompt, result::Result};
+pub use crate::{node::AsyncNode, node::Node, params::Params, prompt::Prompt, result::Result};
+pub use futures::future::BoxFuture;
 pub use serde::{Deserialize, Serialize};
 pub use serde_json::Value;
 pub use std::any::Any;
@@ -1,7 +1,15 @@
-use reqwest::{header::HeaderMap, Method, Url};
+use futures::future::{self, Either};
+use reqwest::{
+    header::{HeaderMap, HeaderName, HeaderValue, CONTENT_TYPE},
+    Method, StatusCode, Url,
+};
 use std::collections::HashMap;
+use std::time::Duration;
 
 use super::prelude::*;
+use crate::utils;
+
+const FORM_CONTENT_TYPE: &str = "application/x-www-form-urlencoded";
 
 const fn _default_timeout() -> u64 {
     60
@@ -15,16 +23,28 @@ pub struct Request {
     pub method: RequestMethod,
     #[serde(default)]
     pub content_type: RequestContentType,
+    // null sends no body
     #[serde(default)]
     pub body: Value,
+    // appended to the url query
+    #[serde(default)]
+    pub query: HashMap<String, String>,
     #[serde(default)]
     pub headers: HashMap<String, String>,
+    #[serde(default)]
+    pub auth: Option<RequestAuth>,
+    // seconds, 0 waits forever
     #[serde(default = "_default_timeout")]
     pub timeout: u64,
+    #[serde(default)]
+    pub response: ResponseMode,
+    #[serde(default)]
+    pub status_policy: StatusPolicy,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
 pub enum RequestMethod {
+    #[default]
     Get,
     Post,
     Put,
@@ -32,18 +52,6 @@ pub enum RequestMethod {
     Patch,
 }
 
-impl Default for RequestMethod {
-    fn default() -> Self {
-        Self::Get
-    }
-}
-
-impl Default for RequestContentType {
-    fn default() -> Self {
-        Self::Json
-    }
-}
-
 impl From<RequestMethod> for Method {
     fn from(method: RequestMethod) -> Self {
         match method {
@@ -56,37 +64,278 @@ impl From<RequestMethod> for Method {
     }
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
 pub enum RequestContentType {
+    // object fields are url encoded, a string is sent as is
     Form,
+    #[default]
     Json,
+    // the body as text, set the Content-Type header yourself
+    Raw,
+    // object fields become parts, `{ "file": "<path>" }` uploads a file
+    Multipart,
 }
 
-#[typetag::serde(name = "Request")]
-impl Node for Request {
-    fn execute(&self, mut params: Params) -> Result<Params> {
-        let url = params.render_template(&self.url);
-        let body = params.render_value(self.body.clone());
-        let headers = self
-            .headers
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(tag = "type")]
+pub enum RequestAuth {
+    Basic {
+        #[serde(default)]
+        username: String,
+        #[serde(default)]
+        password: Option<String>,
+    },
+    Bearer {
+        #[serde(default)]
+        token: String,
+    },
+}
+
+/// What the node outputs from the response.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(tag = "type")]
+pub enum ResponseMode {
+    // the parsed body, null if it is empty
+    #[default]
+    Json,
+    Text,
+    // writes the body to `path` and outputs the path
+    File {
+        #[serde(default)]
+        path: String,
+    },
+    // `{ "status", "headers", "body" }`, body is parsed as json when possible
+    Full,
+}
+
+/// What to do with non-2xx responses.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(tag = "type")]
+pub enum StatusPolicy {
+    #[default]
+    Fail,
+    // keep going and put the status code in a var
+    Var {
+        #[serde(default = "_default_status_var")]
+        name: String,
+    },
+}
+
+fn _default_status_var() -> String {
+    "status".to_string()
+}
+
+/// A response with the body already read.
+pub struct Response {
+    pub status: StatusCode,
+    pub headers: HeaderMap,
+    pub body: Vec<u8>,
+}
+
+fn object_fields(body: &Value) -> Result<Vec<(String, &Value)>> {
+    match body {
+        Value::Object(fields) => Ok(fields.iter().map(|(k, v)| (k.clone(), v)).collect()),
+        _ => Err(crate::Error::new(format!(
+            "request body is not an object: {}",
+            body
+        ))),
+    }
+}
+
+impl Request {
+    /// Renders the request with `params` into a request of `client`.
+    async fn build(
+        &self,
+        client: &reqwest::Client,
+        params: &Params,
+    ) -> Result<reqwest::RequestBuilder> {
+        let url = Url::parse(&params.render_template(&self.url))?;
+        let query = self
+            .query
             .iter()
             .map(|(k, v)| (k.clone(), params.render_template(v)))
-            .collect::<HashMap<String, String>>();
-        let headers: HeaderMap = (&headers).try_into().unwrap_or_default();
+            .collect::<Vec<_>>();
+        let body = params.render_value(self.body.clone());
+
+        let mut headers = HeaderMap::new();
+        for (k, v) in &self.headers {
+            let name = HeaderName::from_bytes(k.as_bytes())
+                .map_err(|err| crate::Error::new(format!("invalid header name {}: {}", k, err)))?;
+            let value = HeaderValue::from_str(&params.render_template(v)).map_err(|err| {
+                crate::Error::new(format!("invalid header value for {}: {}", k, err))
+            })?;
+            headers.insert(name, value);
+        }
+        if let (RequestContentType::Form, Value::String(_)) = (&self.content_type, &body) {
+            let form = HeaderValue::from_static(FORM_CONTENT_TYPE);
+            headers.entry(CONTENT_TYPE).or_insert(form);
+        }
 
-        let url = Url::parse(&url)?;
-        let client = reqwest::blocking::Client::new();
-        let res = client
+        let mut builder = client
             .request(self.method.clone().into(), url)
-            .headers(headers)
-            .body(body.to_string())
-            .send()?
-            .json::<Value>()?;
+            .query(&query)
+            .headers(headers);
+        if self.timeout > 0 {
+            builder = builder.timeout(Duration::from_secs(self.timeout));
+        }
+        builder = match &self.auth {
+            Some(RequestAuth::Basic { username, password }) => builder.basic_auth(
+                params.render_template(username),
+                password
+                    .as_ref()
+                    .map(|password| params.render_template(password)),
+            ),
+            Some(RequestAuth::Bearer { token }) => {
+                builder.bearer_auth(params.render_template(token))
+            }
+            None => builder,
+        };
+
+        let builder = match (&self.content_type, body) {
+            (_, Value::Null) => builder,
+            (RequestContentType::Json, body) => builder.json(&body),
+            (RequestContentType::Form | RequestContentType::Raw, Value::String(body)) => {
+                builder.body(body)
+            }
+            (RequestContentType::Raw, body) => builder.body(body.to_string()),
+            (RequestContentType::Form, body) => builder.form(
+                &object_fields(&body)?
+                    .into_iter()
+                    .map(|(k, v)| (k, utils::value_to_string(v)))
+                    .collect::<Vec<_>>(),
+            ),
+            (RequestContentType::Multipart, body) => {
+                let mut form = reqwest::multipart::Form::new();
+                for (name, value) in object_fields(&body)? {
+                    form = match value.get("file").and_then(Value::as_str) {
+                        Some(path) => {
+                            let path = std::path::Path::new(path);
+                            let file_name = path
+                                .file_name()
+                                .map(|name| name.to_string_lossy().into_owned())
+                                .unwrap_or_default();
+                            let part =
+                                reqwest::multipart::Part::bytes(tokio::fs::read(path).await?)
+                                    .file_name(file_name);
+                            form.part(name, part)
+                        }
+                        None => form.text(name, utils::value_to_string(value)),
+                    };
+                }
+                builder.multipart(form)
+            }
+        };
+        Ok(builder)
+    }
+
+    /// Sends the request, failing on non-2xx responses unless they are passed on as a var.
+    /// Cancelling the run drops the request in flight.
+    pub async fn send_async(&self, params: &Params) -> Result<Response> {
+        let send = async {
+            let client = reqwest::Client::new();
+            let res = self.build(&client, params).await?.send().await?;
+            let status = res.status();
+            let headers = res.headers().clone();
+            let body = res.bytes().await?.to_vec();
+            self.check_status(Response {
+                status,
+                headers,
+                body,
+            })
+        };
+        let cancelled = params.cancellation().cancelled();
+        futures::pin_mut!(send, cancelled);
+        match future::select(send, cancelled).await {
+            Either::Left((res, _)) => res,
+            Either::Right(_) => Err(crate::Error::Cancelled),
+        }
+    }
+
+    /// Blocking [`Request::send_async`] on a runtime of its own, don't call it from async code.
+    pub fn send(&self, params: &Params) -> Result<Response> {
+        tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()?
+            .block_on(self.send_async(params))
+    }
+
+    fn check_status(&self, res: Response) -> Result<Response> {
+        if res.status.is_success() || !matches!(self.status_policy, StatusPolicy::Fail) {
+            return Ok(res);
+        }
+        Err(crate::Error::HttpStatus {
+            status: res.status.as_u16(),
+            body: String::from_utf8_lossy(&res.body).into_owned(),
+        })
+    }
+
+    /// Turns the response into the output value according to the response mode.
+    pub fn output(&self, params: &Params, res: Response) -> Result<Value> {
+        let value = match &self.response {
+            ResponseMode::Json if res.body.is_empty() => Value::Null,
+            ResponseMode::Json => serde_json::from_slice(&res.body)?,
+            ResponseMode::Text => Value::String(String::from_utf8_lossy(&res.body).into_owned()),
+            ResponseMode::File { path } => {
+                let path = params.render_template(path);
+                std::fs::write(&path, &res.body)?;
+                Value::String(path)
+            }
+            ResponseMode::Full => {
+                let headers = res
+                    .headers
+                    .iter()
+                    .map(|(k, v)| {
+                        let v = String::from_utf8_lossy(v.as_bytes()).into_owned();
+                        (k.to_string(), Value::String(v))
+                    })
+                    .collect::<serde_json::Map<_, _>>();
+                let body = serde_json::from_slice(&res.body).unwrap_or_else(|_| {
+                    Value::String(String::from_utf8_lossy(&res.body).into_owned())
+                });
+                serde_json::json!({
+                    "status": res.status.as_u16(),
+                    "headers": headers,
+                    "body": body,
+                })
+            }
+        };
+        Ok(value)
+    }
+
+    /// Sets the status var when the status policy asks for it.
+    pub fn set_status_var(&self, params: &mut Params, res: &Response) {
+        if let StatusPolicy::Var { name } = &self.status_policy {
+            params.set_var(name, res.status.as_u16().into());
+        }
+    }
 
-        params.set_value(res);
+    fn apply(&self, mut params: Params, res: Response) -> Result<Params> {
+        self.set_status_var(&mut params, &res);
+        let value = self.output(&params, res)?;
+        params.set_value(value);
         Ok(params)
     }
+}
+
+#[typetag::serde(name = "Request")]
+impl Node for Request {
+    fn execute(&self, params: Params) -> Result<Params> {
+        let res = self.send(&params)?;
+        self.apply(params, res)
+    }
     fn as_any(&self) -> &dyn Any {
         self
     }
+    fn as_async(&self) -> Option<&dyn AsyncNode> {
+        Some(self)
+    }
+}
+
+impl AsyncNode for Request {
+    fn execute_async(&self, params: Params) -> BoxFuture<'_, Result<Params>> {
+        Box::pin(async move {
+            let res = self.send_async(&params).await?;
+            self.apply(params, res)
+        })
+    }
 }
@@ -0,0 +1,97 @@
+use super::list_filter::ListOption;
+use super::prelude::*;
+use super::request::{Request, Response};
+use crate::utils;
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct RequestFilter {
+    pub title: Option<String>,
+    #[serde(flatten)]
+    pub request: Request,
+    // path to the result array in the response, defaults to the response itself
+    #[serde(default)]
+    pub items_path: Option<String>,
+    // paths into each result item
+    #[serde(default)]
+    pub title_path: String,
+    // defaults to the whole item
+    #[serde(default)]
+    pub value_path: Option<String>,
+    #[serde(default)]
+    pub description_path: Option<String>,
+}
+
+impl RequestFilter {
+    /// Maps the items of the response to options.
+    fn options(&self, params: &mut Params, res: Response) -> Result<Vec<ListOption>> {
+        self.request.set_status_var(params, &res);
+        let res = self.request.output(params, res)?;
+        let items = match &self.items_path {
+            Some(path) => utils::json_path(&res, path).unwrap_or(&Value::Null),
+            None => &res,
+        };
+        let items = items.as_array().ok_or_else(|| {
+            crate::Error::new(format!("response items is not an array: {}", items))
+        })?;
+
+        let options = items
+            .iter()
+            .map(|item| {
+                let get = |path: &str| utils::json_path(item, path).unwrap_or(&Value::Null);
+                ListOption {
+                    title: utils::value_to_string(get(&self.title_path)),
+                    value: match &self.value_path {
+                        Some(path) => get(path).clone(),
+                        None => item.clone(),
+                    },
+                    description: self
+                        .description_path
+                        .as_ref()
+                        .map(|path| utils::value_to_string(get(path))),
+                    mark: None,
+                    highlights: None,
+                }
+            })
+            .collect();
+        Ok(options)
+    }
+
+    /// Prompts the options of the response, a failed request is shown as an error option.
+    fn prompt(&self, mut params: Params, res: Result<Response>) -> Result<Params> {
+        let options = match res.and_then(|res| self.options(&mut params, res)) {
+            Ok(options) => options,
+            Err(crate::Error::Cancelled) => return Err(crate::Error::Cancelled),
+            Err(err) => vec![ListOption::error(err.to_string())],
+        };
+
+        params.set_prompt(Prompt::Select {
+            text: self.title.clone().unwrap_or_default(),
+            options,
+            default: Some(0),
+        });
+        Ok(params)
+    }
+}
+
+#[typetag::serde(name = "RequestFilter")]
+impl Node for RequestFilter {
+    fn execute(&self, params: Params) -> Result<Params> {
+        let res = self.request.send(&params);
+        self.prompt(params, res)
+    }
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn as_async(&self) -> Option<&dyn AsyncNode> {
+        Some(self)
+    }
+}
+
+impl AsyncNode for RequestFilter {
+    fn execute_async(&self, params: Params) -> BoxFuture<'_, Result<Params>> {
+        Box::pin(async move {
+            let res = self.request.send_async(&params).await;
+            self.prompt(params, res)
+        })
+    }
+}
@@ -0,0 +1,82 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use super::prelude::*;
+use crate::utils::{self, CommandOptions};
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct RunScript {
+    #[serde(default)]
+    pub interpreter: Interpreter,
+    // command line or inline script, rendered as template
+    #[serde(default)]
+    pub script: String,
+    #[serde(default)]
+    pub env: HashMap<String, String>,
+    #[serde(default)]
+    pub cwd: Option<String>,
+    // seconds
+    #[serde(default)]
+    pub timeout: Option<u64>,
+    // write the current value to stdin
+    #[serde(default)]
+    pub stdin: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub enum Interpreter {
+    #[default]
+    Sh,
+    Bash,
+    Python,
+    Node,
+}
+
+impl Interpreter {
+    /// The current value is passed as the first script argument (`$1`, `sys.argv[1]`,
+    /// `process.argv[1]`).
+    fn command<'a>(&self, script: &'a str, arg: &'a str) -> (&'static str, Vec<&'a str>) {
+        match self {
+            Interpreter::Sh => ("sh", vec!["-c", script, "sh", arg]),
+            Interpreter::Bash => ("bash", vec!["-c", script, "bash", arg]),
+            Interpreter::Python => ("python3", vec!["-c", script, arg]),
+            Interpreter::Node => ("node", vec!["-e", script, arg]),
+        }
+    }
+}
+
+impl RunScript {
+    /// Runs the script and returns its stdout.
+    pub fn run(&self, params: &Params) -> Result<String> {
+        let script = params.render_template(&self.script);
+        let value = utils::value_to_string(params.get_value());
+        let options = CommandOptions {
+            envs: self
+                .env
+                .iter()
+                .map(|(k, v)| (k.clone(), params.render_template(v)))
+                .collect(),
+            cwd: self
+                .cwd
+                .as_ref()
+                .map(|cwd| params.render_template(cwd).into()),
+            stdin: self.stdin.then(|| value.clone()),
+            timeout: self.timeout.map(Duration::from_secs),
+            cancellation: Some(params.cancellation().clone()),
+        };
+        let (cmd, args) = self.interpreter.command(&script, &value);
+        utils::execute_with(cmd, &args, options)
+    }
+}
+
+#[typetag::serde(name = "RunScript")]
+impl Node for RunScript {
+    fn execute(&self, mut params: Params) -> Result<Params> {
+        let output = self.run(&params)?;
+        params.set_value_str(output.trim_end_matches(['\r', '\n']));
+        Ok(params)
+    }
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
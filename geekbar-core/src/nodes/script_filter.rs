@@ -0,0 +1,53 @@
+use super::list_filter::ListOption;
+use super::prelude::*;
+use super::run_script::RunScript;
+
+/// Runs a script with the query and shows its stdout, a JSON array of `ListOption`, as a list.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ScriptFilter {
+    pub title: Option<String>,
+    #[serde(flatten)]
+    pub script: RunScript,
+    // let the frontend filter the options instead of the script
+    #[serde(default)]
+    pub fuzzy: bool,
+}
+
+impl ScriptFilter {
+    fn fetch_options(&self, params: &Params) -> Result<Vec<ListOption>> {
+        let output = self.script.run(params)?;
+        let options = serde_json::from_str(&output)?;
+        Ok(options)
+    }
+}
+
+#[typetag::serde(name = "ScriptFilter")]
+impl Node for ScriptFilter {
+    fn execute(&self, mut params: Params) -> Result<Params> {
+        let options = match self.fetch_options(&params) {
+            Ok(options) => options,
+            Err(crate::Error::Cancelled) => return Err(crate::Error::Cancelled),
+            Err(err) => vec![ListOption::error(err.to_string())],
+        };
+
+        let text = self.title.clone().unwrap_or_default();
+        let prompt = if self.fuzzy {
+            Prompt::FuzzySelect {
+                text,
+                options,
+                default: Some(0),
+            }
+        } else {
+            Prompt::Select {
+                text,
+                options,
+                default: Some(0),
+            }
+        };
+        params.set_prompt(prompt);
+        Ok(params)
+    }
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
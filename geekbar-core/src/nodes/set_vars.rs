@@ -4,14 +4,44 @@ use super::prelude::*;
 
 #[derive(Serialize, Deserialize, Debug)]
 pub struct SetVars {
+    // vars to set, values are rendered as templates
     #[serde(default)]
     vars: HashMap<String, Value>,
+    // vars to remove
+    #[serde(default)]
+    remove: Vec<String>,
+    // remove all vars before setting new ones
+    #[serde(default)]
+    clear: bool,
+    // store the current value into this var
+    #[serde(default)]
+    capture: Option<String>,
 }
 
 #[typetag::serde(name = "SetVars")]
 impl Node for SetVars {
-    fn execute(&self, _params: Params) -> Result<Params> {
-        unimplemented!()
+    fn execute(&self, mut params: Params) -> Result<Params> {
+        // render against the incoming vars, so the order of `vars` doesn't matter
+        let vars = self
+            .vars
+            .iter()
+            .map(|(key, value)| (key.clone(), params.render_value(value.clone())))
+            .collect::<Vec<_>>();
+
+        if self.clear {
+            params.clear_vars();
+        }
+        for key in &self.remove {
+            params.remove_var(key);
+        }
+        if let Some(key) = &self.capture {
+            let value = params.get_value().clone();
+            params.set_var(key, value);
+        }
+        for (key, value) in vars {
+            params.set_var(&key, value);
+        }
+        Ok(params)
     }
     fn as_any(&self) -> &dyn Any {
         self
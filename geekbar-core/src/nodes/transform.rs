@@ -0,0 +1,212 @@
+use regex::Regex;
+use std::collections::HashMap;
+
+use super::prelude::*;
+use crate::utils;
+
+/// Reshapes the current value, e.g. a `Request` response, without a script.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Transform {
+    // applied in order, the result becomes the value, which is kept if there are none
+    #[serde(default)]
+    pub steps: Vec<TransformStep>,
+    // vars to set, each from its own steps applied to the incoming value
+    #[serde(default)]
+    pub vars: HashMap<String, Vec<TransformStep>>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(tag = "type")]
+pub enum TransformStep {
+    // JSON pointer (`/items/0/name`) or dot path (`items.0.name`), null if missing
+    Pick {
+        #[serde(default)]
+        path: String,
+    },
+    // applies the steps to every item of an array
+    Map {
+        #[serde(default)]
+        steps: Vec<TransformStep>,
+    },
+    // builds an object, each field from its own steps, e.g. `{ title, value }` options
+    Object {
+        #[serde(default)]
+        fields: HashMap<String, Vec<TransformStep>>,
+    },
+    Split {
+        #[serde(default)]
+        separator: String,
+    },
+    Join {
+        #[serde(default)]
+        separator: String,
+    },
+    // the first match, or all of them as an array if `all`, null if nothing matches:
+    // named groups become an object, other groups an array, no groups the matched text
+    Regex {
+        #[serde(default)]
+        pattern: String,
+        #[serde(default)]
+        all: bool,
+    },
+}
+
+impl TransformStep {
+    /// Applies the step to a batch of values, so regexes are compiled once for all items.
+    fn apply(&self, values: Vec<Value>) -> Result<Vec<Value>> {
+        let values = match self {
+            TransformStep::Pick { path } => values
+                .iter()
+                .map(|value| utils::json_path(value, path).cloned().unwrap_or_default())
+                .collect(),
+            TransformStep::Map { steps } => values
+                .into_iter()
+                .map(|value| match value {
+                    Value::Array(items) => Ok(Value::Array(apply_steps(steps, items)?)),
+                    value => Err(crate::Error::new(format!(
+                        "transform map expects an array: {}",
+                        value
+                    ))),
+                })
+                .collect::<Result<_>>()?,
+            TransformStep::Object { fields } => {
+                let mut objects = vec![serde_json::Map::new(); values.len()];
+                for (key, steps) in fields {
+                    let field_values = apply_steps(steps, values.clone())?;
+                    for (object, value) in objects.iter_mut().zip(field_values) {
+                        object.insert(key.clone(), value);
+                    }
+                }
+                objects.into_iter().map(Value::Object).collect()
+            }
+            TransformStep::Split { separator } => values
+                .iter()
+                .map(|value| {
+                    utils::value_to_string(value)
+                        .split(separator.as_str())
+                        .map(|part| Value::String(part.to_string()))
+                        .collect()
+                })
+                .collect(),
+            TransformStep::Join { separator } => values
+                .into_iter()
+                .map(|value| match value {
+                    Value::Array(items) => Ok(Value::String(
+                        items
+                            .iter()
+                            .map(utils::value_to_string)
+                            .collect::<Vec<_>>()
+                            .join(separator),
+                    )),
+                    value => Err(crate::Error::new(format!(
+                        "transform join expects an array: {}",
+                        value
+                    ))),
+                })
+                .collect::<Result<_>>()?,
+            TransformStep::Regex { pattern, all } => {
+                let regex = Regex::new(pattern)?;
+                values
+                    .iter()
+                    .map(|value| {
+                        let text = utils::value_to_string(value);
+                        if *all {
+                            regex
+                                .captures_iter(&text)
+                                .map(|captures| captures_to_value(&regex, &captures))
+                                .collect()
+                        } else {
+                            regex
+                                .captures(&text)
+                                .map(|captures| captures_to_value(&regex, &captures))
+                                .unwrap_or_default()
+                        }
+                    })
+                    .collect()
+            }
+        };
+        Ok(values)
+    }
+
+    fn invalid_regexes(&self) -> Vec<String> {
+        match self {
+            TransformStep::Map { steps } => invalid_regexes(steps),
+            TransformStep::Object { fields } => fields
+                .values()
+                .flat_map(|steps| invalid_regexes(steps))
+                .collect(),
+            TransformStep::Regex { pattern, .. } => match Regex::new(pattern) {
+                Ok(_) => Vec::new(),
+                Err(err) => vec![format!("invalid regex \"{}\": {}", pattern, err)],
+            },
+            _ => Vec::new(),
+        }
+    }
+}
+
+fn apply_steps(steps: &[TransformStep], values: Vec<Value>) -> Result<Vec<Value>> {
+    steps
+        .iter()
+        .try_fold(values, |values, step| step.apply(values))
+}
+
+fn transform(steps: &[TransformStep], value: Value) -> Result<Value> {
+    Ok(apply_steps(steps, vec![value])?.pop().unwrap_or_default())
+}
+
+fn invalid_regexes(steps: &[TransformStep]) -> Vec<String> {
+    steps
+        .iter()
+        .flat_map(TransformStep::invalid_regexes)
+        .collect()
+}
+
+fn captures_to_value(regex: &Regex, captures: &regex::Captures) -> Value {
+    let group = |m: Option<regex::Match>| {
+        m.map(|m| Value::String(m.as_str().to_string()))
+            .unwrap_or_default()
+    };
+    if regex.captures_len() == 1 {
+        return group(captures.get(0));
+    }
+    let names = regex.capture_names().flatten().collect::<Vec<_>>();
+    if names.is_empty() {
+        return captures.iter().skip(1).map(group).collect();
+    }
+    names
+        .into_iter()
+        .map(|name| (name.to_string(), group(captures.name(name))))
+        .collect()
+}
+
+impl Transform {
+    /// Regexes that don't compile, as messages.
+    pub fn invalid_regexes(&self) -> Vec<String> {
+        let mut messages = invalid_regexes(&self.steps);
+        for steps in self.vars.values() {
+            messages.extend(invalid_regexes(steps));
+        }
+        messages
+    }
+}
+
+#[typetag::serde(name = "Transform")]
+impl Node for Transform {
+    fn execute(&self, mut params: Params) -> Result<Params> {
+        let vars = self
+            .vars
+            .iter()
+            .map(|(key, steps)| Ok((key.clone(), transform(steps, params.get_value().clone())?)))
+            .collect::<Result<Vec<_>>>()?;
+
+        let value = transform(&self.steps, params.get_value().clone())?;
+        params.set_value(value);
+        for (key, value) in vars {
+            params.set_var(&key, value);
+        }
+        Ok(params)
+    }
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
@@ -15,6 +15,49 @@ pub enum Trigger {
         #[serde(default)]
         title: String,
     },
+    Schedule {
+        // cron expression with seconds, e.g. "0 30 9 * * Mon-Fri"
+        #[serde(default)]
+        cron: Option<String>,
+        // fixed interval in seconds, used if no cron is given
+        #[serde(default)]
+        interval: Option<u64>,
+        #[serde(default)]
+        title: String,
+    },
+    FileWatch {
+        // file or directory to watch, a leading "~" is the home dir
+        #[serde(default)]
+        path: String,
+        #[serde(default = "_default_file_events")]
+        events: Vec<FileEvent>,
+        // only fire for changed paths matching this pattern, e.g. "*.png"
+        #[serde(default)]
+        glob: Option<String>,
+        #[serde(default)]
+        recursive: bool,
+        // milliseconds a path must stay unchanged before firing,
+        // so saving a file fires once instead of for every write
+        #[serde(default = "_default_debounce")]
+        debounce: u64,
+        #[serde(default)]
+        title: String,
+    },
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileEvent {
+    Create,
+    Modify,
+    Remove,
+}
+
+fn _default_file_events() -> Vec<FileEvent> {
+    vec![FileEvent::Create, FileEvent::Modify]
+}
+
+fn _default_debounce() -> u64 {
+    300
 }
 
 #[typetag::serde(name = "Trigger")]
@@ -45,12 +88,28 @@ impl Trigger {
                 });
                 keys.join("+")
             }
+            Trigger::Schedule { cron, interval, .. } => match (cron, interval) {
+                (Some(cron), _) => format!("@cron {}", cron),
+                (None, Some(interval)) => format!("@every {}s", interval),
+                (None, None) => "@never".to_string(),
+            },
+            Trigger::FileWatch { path, glob, .. } => match glob {
+                Some(glob) => format!("@watch {} {}", path, glob),
+                None => format!("@watch {}", path),
+            },
         }
     }
+    /// Whether the trigger is fired by its id, schedules and file watches are fired by
+    /// their node, so workflows with the same schedule or path don't share it.
+    pub fn fires_by_id(&self) -> bool {
+        !matches!(self, Trigger::Schedule { .. } | Trigger::FileWatch { .. })
+    }
     pub fn title(&self) -> &str {
         match self {
             Trigger::Keyword { title, .. } => title,
             Trigger::Shortcut { title, .. } => title,
+            Trigger::Schedule { title, .. } => title,
+            Trigger::FileWatch { title, .. } => title,
         }
     }
 }
@@ -2,7 +2,7 @@ use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::collections::HashMap;
 
-use crate::prompt::Prompt;
+use crate::{cancel::CancellationToken, prompt::Prompt};
 
 pub type Context = HashMap<String, Value>;
 
@@ -11,6 +11,9 @@ pub struct Params {
     inner: Value,
     pub context: Context,
     pub prompt: Option<Prompt>,
+    // set by the executor, long-running nodes should stop once it is cancelled
+    #[serde(skip)]
+    cancellation: CancellationToken,
 }
 
 impl Default for Params {
@@ -19,6 +22,7 @@ impl Default for Params {
             context: Default::default(),
             inner: Value::Null,
             prompt: None,
+            cancellation: CancellationToken::default(),
         }
     }
 }
@@ -29,6 +33,7 @@ impl Params {
             context: Context::new(),
             inner: value,
             prompt: None,
+            cancellation: CancellationToken::default(),
         }
     }
 
@@ -36,6 +41,14 @@ impl Params {
         self.context.insert(key.to_string(), value);
     }
 
+    pub fn remove_var(&mut self, key: &str) -> Option<Value> {
+        self.context.remove(key)
+    }
+
+    pub fn clear_vars(&mut self) {
+        self.context.clear();
+    }
+
     pub fn get_var(&self, key: &str) -> Option<&Value> {
         self.context.get(key)
     }
@@ -68,6 +81,18 @@ impl Params {
         self.prompt.take()
     }
 
+    pub fn cancellation(&self) -> &CancellationToken {
+        &self.cancellation
+    }
+
+    pub fn set_cancellation(&mut self, cancellation: CancellationToken) {
+        self.cancellation = cancellation;
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancellation.is_cancelled()
+    }
+
     pub fn render_value(&self, value: Value) -> Value {
         match value {
             Value::String(s) => Value::String(self.render_template(&s)),
@@ -80,6 +105,14 @@ impl Params {
         }
     }
 
+    /// The `locale` var if set, otherwise the system language.
+    pub fn locale(&self) -> String {
+        match self.get_var("locale") {
+            Some(Value::String(locale)) => locale.clone(),
+            _ => crate::utils::locale(),
+        }
+    }
+
     pub fn render_template(&self, template: &str) -> String {
         crate::utils::render_template(template, self)
     }
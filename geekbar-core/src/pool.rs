@@ -0,0 +1,49 @@
+use std::{
+    panic::{self, AssertUnwindSafe},
+    thread,
+};
+
+use crossbeam_channel::Sender;
+
+type Job = Box<dyn FnOnce() + Send + 'static>;
+
+/// Fixed size thread pool that runs node executions.
+pub struct WorkerPool {
+    sender: Sender<Job>,
+}
+
+impl WorkerPool {
+    pub fn new(size: usize) -> Self {
+        let (sender, receiver) = crossbeam_channel::unbounded::<Job>();
+        for i in 0..size.max(1) {
+            let receiver = receiver.clone();
+            thread::Builder::new()
+                .name(format!("geekbar-worker-{}", i))
+                .spawn(move || {
+                    // workers exit once the pool is dropped
+                    while let Ok(job) = receiver.recv() {
+                        // a panicking node must not take the worker down with it
+                        if panic::catch_unwind(AssertUnwindSafe(job)).is_err() {
+                            tracing::error!("worker job panicked");
+                        }
+                    }
+                })
+                .expect("failed to spawn worker thread");
+        }
+        Self { sender }
+    }
+
+    pub fn execute(&self, job: impl FnOnce() + Send + 'static) {
+        // workers live as long as the pool, so sending never fails
+        self.sender.send(Box::new(job)).ok();
+    }
+}
+
+impl Default for WorkerPool {
+    fn default() -> Self {
+        let size = thread::available_parallelism()
+            .map(|n| n.get())
+            .unwrap_or(4);
+        Self::new(size)
+    }
+}
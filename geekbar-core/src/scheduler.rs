@@ -0,0 +1,181 @@
+use std::{
+    collections::HashMap,
+    str::FromStr,
+    sync::{Arc, Condvar, Mutex},
+    thread,
+};
+
+use chrono::{DateTime, Local};
+use crossbeam_channel::{Receiver, Sender};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{nodes::trigger::Trigger, workflow::Workflow};
+
+/// A `Trigger::Schedule` node, schedules belong to their workflow even if
+/// another one has the same cron or interval.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ScheduledTrigger {
+    pub workflow_id: Uuid,
+    pub node_id: Uuid,
+}
+
+pub type ScheduleSender = Sender<ScheduledTrigger>;
+/// Receives the `Trigger::Schedule`s that are due,
+/// frontends pass them to `Executor::trigger_node`.
+pub type ScheduleReceiver = Receiver<ScheduledTrigger>;
+
+enum Plan {
+    Cron(Box<cron::Schedule>),
+    Interval(chrono::Duration),
+}
+
+impl Plan {
+    fn from_trigger(trigger: &Trigger) -> crate::result::Result<Option<Self>> {
+        match trigger {
+            Trigger::Schedule {
+                cron: Some(cron), ..
+            } => Ok(Some(Plan::Cron(Box::new(cron::Schedule::from_str(cron)?)))),
+            Trigger::Schedule {
+                interval: Some(interval),
+                ..
+            } if *interval > 0 => Ok(Some(Plan::Interval(chrono::Duration::seconds(
+                *interval as i64,
+            )))),
+            _ => Ok(None),
+        }
+    }
+
+    fn next_after(&self, time: &DateTime<Local>) -> Option<DateTime<Local>> {
+        match self {
+            Plan::Cron(schedule) => schedule.after(time).next(),
+            Plan::Interval(interval) => Some(*time + *interval),
+        }
+    }
+}
+
+struct Job {
+    plan: Plan,
+    next: Option<DateTime<Local>>,
+}
+
+#[derive(Default)]
+struct State {
+    jobs: HashMap<ScheduledTrigger, Job>,
+    stopped: bool,
+}
+
+/// Timer service that fires `Trigger::Schedule`s of all active workflows.
+pub struct Scheduler {
+    state: Arc<(Mutex<State>, Condvar)>,
+    receiver: ScheduleReceiver,
+}
+
+impl Default for Scheduler {
+    fn default() -> Self {
+        let (sender, receiver) = crossbeam_channel::unbounded();
+        let state = Arc::new((Mutex::new(State::default()), Condvar::new()));
+
+        let thread_state = state.clone();
+        thread::spawn(move || Self::run(thread_state, sender));
+
+        Self { state, receiver }
+    }
+}
+
+impl Drop for Scheduler {
+    fn drop(&mut self) {
+        let (lock, condvar) = &*self.state;
+        lock.lock().unwrap().stopped = true;
+        condvar.notify_all();
+    }
+}
+
+impl Scheduler {
+    pub fn receiver(&self) -> &ScheduleReceiver {
+        &self.receiver
+    }
+
+    /// Next fire time of every scheduled trigger.
+    pub fn next_fire_times(&self) -> HashMap<ScheduledTrigger, DateTime<Local>> {
+        let state = self.state.0.lock().unwrap();
+        state
+            .jobs
+            .iter()
+            .filter_map(|(scheduled, job)| job.next.map(|next| (*scheduled, next)))
+            .collect()
+    }
+
+    pub fn add_workflow(&mut self, workflow: &Workflow) {
+        let now = Local::now();
+        let (lock, condvar) = &*self.state;
+        let mut state = lock.lock().unwrap();
+        for (node_id, trigger) in workflow.get_schedules() {
+            match Plan::from_trigger(trigger) {
+                Ok(Some(plan)) => {
+                    let scheduled = ScheduledTrigger {
+                        workflow_id: workflow.id,
+                        node_id,
+                    };
+                    let job = Job {
+                        next: plan.next_after(&now),
+                        plan,
+                    };
+                    state.jobs.insert(scheduled, job);
+                }
+                Ok(None) => {}
+                Err(err) => {
+                    tracing::error!(workflow_id = %workflow.id, %err, "invalid schedule");
+                }
+            }
+        }
+        condvar.notify_all();
+    }
+
+    pub fn remove_workflow(&mut self, workflow_id: Uuid) {
+        let (lock, condvar) = &*self.state;
+        let mut state = lock.lock().unwrap();
+        state
+            .jobs
+            .retain(|scheduled, _| scheduled.workflow_id != workflow_id);
+        condvar.notify_all();
+    }
+
+    fn run(state: Arc<(Mutex<State>, Condvar)>, sender: ScheduleSender) {
+        let (lock, condvar) = &*state;
+        let mut state = lock.lock().unwrap();
+        loop {
+            if state.stopped {
+                return;
+            }
+
+            // fire due jobs and plan their next time
+            let now = Local::now();
+            for (scheduled, job) in state.jobs.iter_mut() {
+                if matches!(job.next, Some(next) if next <= now) {
+                    tracing::info!(
+                        workflow_id = %scheduled.workflow_id,
+                        node_id = %scheduled.node_id,
+                        "schedule fired"
+                    );
+                    if sender.send(*scheduled).is_err() {
+                        return;
+                    }
+                    job.next = job.plan.next_after(&now);
+                }
+            }
+
+            // sleep until the earliest job, or until the jobs change
+            let timeout = state
+                .jobs
+                .values()
+                .filter_map(|job| job.next)
+                .min()
+                .map(|next| (next - now).to_std().unwrap_or_default());
+            state = match timeout {
+                Some(timeout) => condvar.wait_timeout(state, timeout).unwrap().0,
+                None => condvar.wait(state).unwrap(),
+            };
+        }
+    }
+}
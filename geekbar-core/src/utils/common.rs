@@ -1,3 +1,5 @@
+use once_cell::sync::Lazy;
+
 pub fn set_clipboard_content(content: &str) -> crate::result::Result<()> {
     use clipboard::{ClipboardContext, ClipboardProvider};
     let mut ctx: ClipboardContext = ClipboardProvider::new()?;
@@ -50,20 +52,150 @@ pub fn execute_nodejs(script: &str) -> crate::result::Result<String> {
 }
 
 pub fn execute(cmd: &str, args: &[&str]) -> crate::result::Result<String> {
-    use std::process::Command;
+    execute_with(cmd, args, CommandOptions::default())
+}
+
+#[derive(Default)]
+pub struct CommandOptions {
+    pub envs: std::collections::HashMap<String, String>,
+    pub cwd: Option<std::path::PathBuf>,
+    pub stdin: Option<String>,
+    pub timeout: Option<std::time::Duration>,
+    // kills the command once cancelled
+    pub cancellation: Option<crate::cancel::CancellationToken>,
+}
+
+/// 执行命令（支持环境变量、工作目录、标准输入、超时及取消）
+pub fn execute_with(
+    cmd: &str,
+    args: &[&str],
+    options: CommandOptions,
+) -> crate::result::Result<String> {
+    use std::io::Write;
+    use std::process::{Command, Stdio};
+    use std::time::{Duration, Instant};
+
     let mut command = Command::new(cmd);
-    command.args(args);
-    let output = command.output()?;
-    if output.status.success() {
-        Ok(String::from_utf8_lossy(output.stdout.as_ref()).to_string())
+    command
+        .args(args)
+        .envs(&options.envs)
+        .stdin(match options.stdin {
+            Some(_) => Stdio::piped(),
+            None => Stdio::null(),
+        })
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    if let Some(cwd) = &options.cwd {
+        command.current_dir(cwd);
+    }
+    // 放到单独的进程组，超时或取消时连同它派生的子进程一起结束
+    #[cfg(unix)]
+    unsafe {
+        use std::os::unix::process::CommandExt;
+        command.pre_exec(|| {
+            if libc::setpgid(0, 0) == 0 {
+                Ok(())
+            } else {
+                Err(std::io::Error::last_os_error())
+            }
+        });
+    }
+    let mut child = command.spawn()?;
+
+    if let (Some(input), Some(mut stdin)) = (options.stdin, child.stdin.take()) {
+        std::thread::spawn(move || stdin.write_all(input.as_bytes()));
+    }
+    let stdout = read_pipe(child.stdout.take());
+    let stderr = read_pipe(child.stderr.take());
+
+    let status = match (options.timeout, &options.cancellation) {
+        (None, None) => child.wait()?,
+        (timeout, cancellation) => {
+            let deadline = timeout.map(|timeout| Instant::now() + timeout);
+            loop {
+                if let Some(status) = child.try_wait()? {
+                    break status;
+                }
+                if matches!(cancellation, Some(cancellation) if cancellation.is_cancelled()) {
+                    kill_group(&mut child)?;
+                    child.wait()?;
+                    return Err(crate::error::Error::Cancelled);
+                }
+                if matches!(deadline, Some(deadline) if Instant::now() >= deadline) {
+                    kill_group(&mut child)?;
+                    child.wait()?;
+                    return Err(crate::error::Error::new(format!(
+                        "command timed out after {}s",
+                        timeout.unwrap_or_default().as_secs_f32()
+                    )));
+                }
+                std::thread::sleep(Duration::from_millis(10));
+            }
+        }
+    };
+
+    let stdout = stdout.join().unwrap_or_default();
+    if status.success() {
+        Ok(String::from_utf8_lossy(&stdout).to_string())
     } else {
+        let stderr = stderr.join().unwrap_or_default();
         Err(crate::error::Error::Command {
-            status: output.status,
-            message: String::from_utf8_lossy(output.stderr.as_ref()).to_string(),
+            status,
+            message: String::from_utf8_lossy(&stderr).to_string(),
         })
     }
 }
 
+/// 结束子进程所在的进程组
+#[cfg(unix)]
+fn kill_group(child: &mut std::process::Child) -> std::io::Result<()> {
+    match unsafe { libc::kill(-(child.id() as libc::pid_t), libc::SIGKILL) } {
+        0 => Ok(()),
+        _ => child.kill(),
+    }
+}
+
+#[cfg(not(unix))]
+fn kill_group(child: &mut std::process::Child) -> std::io::Result<()> {
+    child.kill()
+}
+
+fn read_pipe(
+    pipe: Option<impl std::io::Read + Send + 'static>,
+) -> std::thread::JoinHandle<Vec<u8>> {
+    std::thread::spawn(move || {
+        let mut buf = Vec::new();
+        if let Some(mut pipe) = pipe {
+            pipe.read_to_end(&mut buf).ok();
+        }
+        buf
+    })
+}
+
+/// 转换成字符串（字符串不带引号，null 为空串）
+pub fn value_to_string(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::String(s) => s.clone(),
+        serde_json::Value::Null => String::new(),
+        _ => value.to_string(),
+    }
+}
+
+/// 按 JSON pointer（如 `/items/0/status`）或点路径（如 `items.0.status`）取值
+pub fn json_path<'a>(value: &'a serde_json::Value, path: &str) -> Option<&'a serde_json::Value> {
+    if path.is_empty() {
+        return Some(value);
+    }
+    if path.starts_with('/') {
+        return value.pointer(path);
+    }
+    path.split('.').try_fold(value, |value, key| match value {
+        serde_json::Value::Object(o) => o.get(key),
+        serde_json::Value::Array(a) => key.parse::<usize>().ok().and_then(|i| a.get(i)),
+        _ => None,
+    })
+}
+
 /// 模拟键盘输出
 pub fn input(s: &str) {
     use enigo::KeyboardControllable;
@@ -103,9 +235,20 @@ pub fn includes_str(origin: &str, target: &str) -> bool {
 
 /// 模糊查询（支持拼音）
 pub fn fuzzy_query(origin: &str, target: &str) -> bool {
-    let origin = origin.to_lowercase();
-    let target = target.to_lowercase();
-    includes_str(&origin, &target) || includes_str(&zh_to_pinyin(&origin), &target)
+    use super::{FuzzyMatcher, Matcher};
+    FuzzyMatcher::default().find(origin, target).is_some()
+}
+
+// 启动后只读取一次
+static LOCALE: Lazy<String> = Lazy::new(|| {
+    sys_locale::get_locale()
+        .filter(|locale| locale != "C" && locale != "POSIX")
+        .unwrap_or_else(|| "en-US".to_string())
+});
+
+/// 系统语言，如 `zh-CN`、`en-US`，取不到时为 `en-US`
+pub fn locale() -> String {
+    LOCALE.clone()
 }
 
 /// 通知
@@ -0,0 +1,236 @@
+use once_cell::sync::Lazy;
+use pinyin::ToPinyin;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+const SCORE_MATCH: i64 = 16;
+const GAP_START: i64 = 3;
+const GAP_EXTENSION: i64 = 1;
+// 单词开头（字符串开头、分隔符之后、每个汉字的拼音）
+const BONUS_BOUNDARY: i64 = 8;
+// 驼峰或数字开头
+const BONUS_CAMEL: i64 = 7;
+const BONUS_CONSECUTIVE: i64 = 4;
+const BONUS_FIRST_CHAR_MULTIPLIER: i64 = 2;
+
+// 超过后整体清空
+const PINYIN_CACHE_SIZE: usize = 4096;
+
+static PINYIN_CACHE: Lazy<Mutex<HashMap<String, Arc<Vec<Unit>>>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// 匹配结果，`positions` 为命中字符的下标（按字符计，升序）
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Match {
+    pub score: i64,
+    pub positions: Vec<usize>,
+}
+
+impl Match {
+    /// 把相邻的命中字符合并为 `[start, end)` 区间，用于高亮
+    pub fn ranges(&self) -> Vec<(usize, usize)> {
+        let mut ranges: Vec<(usize, usize)> = Vec::new();
+        for &position in &self.positions {
+            match ranges.last_mut() {
+                Some((_, end)) if *end == position => *end += 1,
+                _ => ranges.push((position, position + 1)),
+            }
+        }
+        ranges
+    }
+}
+
+/// 匹配器，不匹配时返回 `None`，空关键字匹配一切
+pub trait Matcher: Send + Sync {
+    fn find(&self, text: &str, pattern: &str) -> Option<Match>;
+}
+
+/// 类似 fzf 的模糊匹配（Smith-Waterman 计分），
+/// 开启 `pinyin` 后汉字也可以用全拼或首字母匹配（如 "bd" 匹配 "百度"）
+#[derive(Debug, Clone)]
+pub struct FuzzyMatcher {
+    pub pinyin: bool,
+}
+
+impl Default for FuzzyMatcher {
+    fn default() -> Self {
+        Self { pinyin: true }
+    }
+}
+
+impl Matcher for FuzzyMatcher {
+    fn find(&self, text: &str, pattern: &str) -> Option<Match> {
+        let pattern = pattern.to_lowercase().chars().collect::<Vec<_>>();
+        let plain = align(&plain_units(text), &pattern);
+        if !self.pinyin || !text.chars().any(|c| c.to_pinyin().is_some()) {
+            return plain;
+        }
+        let pinyin = align(&pinyin_units(text), &pattern);
+        match (plain, pinyin) {
+            (Some(plain), Some(pinyin)) if pinyin.score > plain.score => Some(pinyin),
+            (None, pinyin) => pinyin,
+            (plain, _) => plain,
+        }
+    }
+}
+
+/// 连续子串匹配（不区分大小写）
+#[derive(Debug, Clone, Default)]
+pub struct SubstringMatcher;
+
+impl Matcher for SubstringMatcher {
+    fn find(&self, text: &str, pattern: &str) -> Option<Match> {
+        let units = plain_units(text);
+        let pattern = pattern.to_lowercase().chars().collect::<Vec<_>>();
+        if pattern.is_empty() {
+            return Some(Match::default());
+        }
+        let start = units
+            .windows(pattern.len())
+            .position(|window| window.iter().zip(&pattern).all(|(unit, c)| unit.ch == *c))?;
+        Some(Match {
+            score: SCORE_MATCH * pattern.len() as i64 + units[start].bonus,
+            positions: (start..start + pattern.len()).collect(),
+        })
+    }
+}
+
+/// 参与匹配的字符，`index` 为它在原字符串中的下标
+#[derive(Debug, Clone)]
+struct Unit {
+    ch: char,
+    index: usize,
+    bonus: i64,
+}
+
+fn bonus(prev: Option<char>, cur: char) -> i64 {
+    match prev {
+        None => BONUS_BOUNDARY,
+        Some(prev) if !prev.is_alphanumeric() && cur.is_alphanumeric() => BONUS_BOUNDARY,
+        Some(prev) if prev.is_lowercase() && cur.is_uppercase() => BONUS_CAMEL,
+        Some(prev) if !prev.is_numeric() && cur.is_numeric() => BONUS_CAMEL,
+        _ => 0,
+    }
+}
+
+fn lowercase(c: char) -> char {
+    c.to_lowercase().next().unwrap_or(c)
+}
+
+fn plain_units(text: &str) -> Vec<Unit> {
+    let mut prev = None;
+    text.chars()
+        .enumerate()
+        .map(|(index, c)| {
+            let unit = Unit {
+                ch: lowercase(c),
+                index,
+                bonus: bonus(prev, c),
+            };
+            prev = Some(c);
+            unit
+        })
+        .collect()
+}
+
+/// 汉字展开为拼音字母，都指向该汉字，每个拼音的首字母算作单词开头
+fn pinyin_units(text: &str) -> Arc<Vec<Unit>> {
+    let mut cache = PINYIN_CACHE.lock().unwrap();
+    if let Some(units) = cache.get(text) {
+        return units.clone();
+    }
+
+    let mut units = Vec::new();
+    let mut prev = None;
+    for (index, c) in text.chars().enumerate() {
+        match c.to_pinyin() {
+            Some(pinyin) => {
+                for (i, ch) in pinyin.plain().chars().enumerate() {
+                    let bonus = if i == 0 { BONUS_BOUNDARY } else { 0 };
+                    units.push(Unit { ch, index, bonus });
+                }
+            }
+            None => units.push(Unit {
+                ch: lowercase(c),
+                index,
+                bonus: bonus(prev, c),
+            }),
+        }
+        prev = Some(c);
+    }
+
+    if cache.len() >= PINYIN_CACHE_SIZE {
+        cache.clear();
+    }
+    let units = Arc::new(units);
+    cache.insert(text.to_string(), units.clone());
+    units
+}
+
+/// 求得分最高的对齐方式，`scores[i][j]` 为模式第 i 个字符落在第 j 个位置时的最高分
+fn align(units: &[Unit], pattern: &[char]) -> Option<Match> {
+    if pattern.is_empty() {
+        return Some(Match::default());
+    }
+    // 先确认是子序列，避免无谓的计算
+    let mut rest = units.iter();
+    if !pattern.iter().all(|c| rest.any(|unit| unit.ch == *c)) {
+        return None;
+    }
+
+    const NONE: i64 = i64::MIN / 2;
+    let reachable = |score: i64| score > NONE / 2;
+    let (n, m) = (units.len(), pattern.len());
+    let mut scores = vec![vec![NONE; n]; m];
+    let mut from = vec![vec![0; n]; m];
+    for (i, c) in pattern.iter().enumerate() {
+        // 上一行中隔开至少一个位置的最高分（已扣除空位）
+        let mut gap = NONE;
+        let mut gap_from = 0;
+        for j in 0..n {
+            if i > 0 && j >= 2 {
+                gap -= GAP_EXTENSION;
+                let open = scores[i - 1][j - 2] - GAP_START;
+                if open >= gap {
+                    gap = open;
+                    gap_from = j - 2;
+                }
+            }
+            if units[j].ch != *c {
+                continue;
+            }
+            if i == 0 {
+                scores[i][j] = SCORE_MATCH + units[j].bonus * BONUS_FIRST_CHAR_MULTIPLIER;
+                continue;
+            }
+
+            let (mut best, mut best_from) = (gap, gap_from);
+            if j >= 1 && reachable(scores[i - 1][j - 1]) {
+                let consecutive = scores[i - 1][j - 1] + BONUS_CONSECUTIVE;
+                if consecutive >= best {
+                    best = consecutive;
+                    best_from = j - 1;
+                }
+            }
+            if reachable(best) {
+                scores[i][j] = best + SCORE_MATCH + units[j].bonus;
+                from[i][j] = best_from;
+            }
+        }
+    }
+
+    let (mut j, score) = scores[m - 1]
+        .iter()
+        .copied()
+        .enumerate()
+        .filter(|(_, score)| reachable(*score))
+        .max_by_key(|(j, score)| (*score, std::cmp::Reverse(*j)))?;
+    let mut positions = Vec::with_capacity(m);
+    for i in (0..m).rev() {
+        positions.push(units[j].index);
+        j = from[i][j];
+    }
+    positions.reverse();
+    positions.dedup();
+    Some(Match { score, positions })
+}
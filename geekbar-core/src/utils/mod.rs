@@ -1,5 +1,7 @@
 mod common;
+mod fuzzy;
 mod render_template;
 
 pub use common::*;
+pub use fuzzy::{FuzzyMatcher, Match, Matcher, SubstringMatcher};
 pub use render_template::render_template;
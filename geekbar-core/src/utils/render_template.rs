@@ -55,6 +55,7 @@ pub fn render_template(template: &str, data: &Params) -> String {
         crate::utils::get_clipboard_contents().unwrap_or_default()
     });
     register_helper(&mut reg, "uuid", crate::utils::generate_uuid);
+    register_helper(&mut reg, "locale", || data.locale());
     log::debug!("render_template template: {}", template);
     reg.render_template(template, vars).unwrap_or_default()
 }
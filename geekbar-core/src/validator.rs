@@ -0,0 +1,278 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::executor::CollisionPolicy;
+use crate::nodes::transform::Transform;
+use crate::nodes::trigger::Trigger;
+use crate::workflow::{Workflow, WorkflowConfig};
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum Severity {
+    // the workflow is not loaded
+    Error,
+    // the workflow is loaded anyway
+    Warning,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum DiagnosticKind {
+    DanglingLink,
+    DuplicateNode,
+    Cycle,
+    UnreachableNode,
+    NoTrigger,
+    DuplicateTrigger,
+    InvalidRegex,
+    // the executor refused the workflow
+    LoadFailed,
+}
+
+/// A problem found in a workflow, `nodes` are the ones to highlight.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub kind: DiagnosticKind,
+    pub nodes: Vec<Uuid>,
+    pub message: String,
+}
+
+impl Diagnostic {
+    fn new(severity: Severity, kind: DiagnosticKind, nodes: Vec<Uuid>, message: String) -> Self {
+        Self {
+            severity,
+            kind,
+            nodes,
+            message,
+        }
+    }
+
+    pub fn error(kind: DiagnosticKind, nodes: Vec<Uuid>, message: String) -> Self {
+        Self::new(Severity::Error, kind, nodes, message)
+    }
+
+    fn warning(kind: DiagnosticKind, nodes: Vec<Uuid>, message: String) -> Self {
+        Self::new(Severity::Warning, kind, nodes, message)
+    }
+
+    pub fn is_error(&self) -> bool {
+        self.severity == Severity::Error
+    }
+}
+
+/// Diagnostics of the workflow itself, plus trigger ids already taken by `loaded` workflows.
+pub fn validate(
+    workflow: &Workflow,
+    loaded: &[Arc<Workflow>],
+    policy: CollisionPolicy,
+) -> Vec<Diagnostic> {
+    let mut diagnostics = workflow.diagnostics().to_vec();
+    diagnostics.extend(validate_triggers(workflow, loaded, policy));
+    diagnostics
+}
+
+/// Keyword and shortcut ids shared across workflows, only errors when the policy rejects them.
+pub fn validate_triggers(
+    workflow: &Workflow,
+    loaded: &[Arc<Workflow>],
+    policy: CollisionPolicy,
+) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+    for trigger in workflow
+        .get_keywords()
+        .into_iter()
+        .chain(workflow.get_shortcuts())
+    {
+        let trigger_id = trigger.id();
+        let owners = loaded
+            .iter()
+            .filter(|other| other.id != workflow.id && other.has_trigger(&trigger_id))
+            .map(|other| format!("\"{}\"", other.title))
+            .collect::<Vec<_>>();
+        if !owners.is_empty() {
+            let nodes = workflow
+                .get_triggers()
+                .get(&trigger_id)
+                .copied()
+                .into_iter()
+                .collect();
+            let severity = match policy {
+                CollisionPolicy::Reject => Severity::Error,
+                CollisionPolicy::LastWins | CollisionPolicy::Choose => Severity::Warning,
+            };
+            diagnostics.push(Diagnostic::new(
+                severity,
+                DiagnosticKind::DuplicateTrigger,
+                nodes,
+                format!(
+                    "trigger \"{}\" is already used by {}",
+                    trigger_id,
+                    owners.join(", ")
+                ),
+            ));
+        }
+    }
+    diagnostics
+}
+
+/// Checks that only need the workflow config itself.
+pub fn validate_config(config: &WorkflowConfig) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+
+    // duplicate node ids
+    let mut node_ids = HashSet::new();
+    for node in &config.nodes {
+        if !node_ids.insert(node.id) {
+            diagnostics.push(Diagnostic::error(
+                DiagnosticKind::DuplicateNode,
+                vec![node.id],
+                format!("duplicate node id {}", node.id),
+            ));
+        }
+    }
+
+    // triggers, and duplicate trigger ids inside the workflow
+    let mut trigger_nodes = Vec::new();
+    let mut trigger_ids = HashMap::new();
+    for node in &config.nodes {
+        if let Some(trigger) = node.node.as_any().downcast_ref::<Trigger>() {
+            trigger_nodes.push(node.id);
+            if !trigger.fires_by_id() {
+                continue;
+            }
+            if let Some(other) = trigger_ids.insert(trigger.id(), node.id) {
+                diagnostics.push(Diagnostic::error(
+                    DiagnosticKind::DuplicateTrigger,
+                    vec![other, node.id],
+                    format!("trigger \"{}\" is used twice", trigger.id()),
+                ));
+            }
+        }
+    }
+    for node in &config.nodes {
+        if let Some(transform) = node.node.as_any().downcast_ref::<Transform>() {
+            for message in transform.invalid_regexes() {
+                diagnostics.push(Diagnostic::error(
+                    DiagnosticKind::InvalidRegex,
+                    vec![node.id],
+                    message,
+                ));
+            }
+        }
+    }
+    if trigger_nodes.is_empty() {
+        diagnostics.push(Diagnostic::warning(
+            DiagnosticKind::NoTrigger,
+            Vec::new(),
+            "workflow has no trigger".to_string(),
+        ));
+    }
+
+    // links
+    let mut graph = HashMap::<Uuid, Vec<Uuid>>::new();
+    let mut reachable_graph = HashMap::<Uuid, Vec<Uuid>>::new();
+    for link in &config.links {
+        let missing = [link.from, link.to]
+            .into_iter()
+            .filter(|id| !node_ids.contains(id))
+            .collect::<Vec<_>>();
+        if !missing.is_empty() {
+            let ends = [link.from, link.to]
+                .into_iter()
+                .filter(|id| node_ids.contains(id))
+                .collect();
+            let missing = missing.iter().map(Uuid::to_string).collect::<Vec<_>>();
+            diagnostics.push(Diagnostic::error(
+                DiagnosticKind::DanglingLink,
+                ends,
+                format!(
+                    "link from {} to {} refers to missing node {}",
+                    link.from,
+                    link.to,
+                    missing.join(", ")
+                ),
+            ));
+            continue;
+        }
+
+        if let Some(condition) = &link.condition {
+            for message in condition.invalid_regexes() {
+                diagnostics.push(Diagnostic::error(
+                    DiagnosticKind::InvalidRegex,
+                    vec![link.from, link.to],
+                    message,
+                ));
+            }
+        }
+
+        reachable_graph.entry(link.from).or_default().push(link.to);
+        if !link.allow_cycle {
+            graph.entry(link.from).or_default().push(link.to);
+        }
+    }
+
+    // cycles that are not explicitly allowed
+    let mut visited = HashSet::new();
+    for node in &config.nodes {
+        let mut path = Vec::new();
+        find_cycles(node.id, &graph, &mut visited, &mut path, &mut diagnostics);
+    }
+
+    // nodes that no trigger leads to
+    let mut reached = trigger_nodes.iter().copied().collect::<HashSet<_>>();
+    let mut queue = trigger_nodes;
+    while let Some(id) = queue.pop() {
+        for next in reachable_graph.get(&id).into_iter().flatten() {
+            if reached.insert(*next) {
+                queue.push(*next);
+            }
+        }
+    }
+    for node in &config.nodes {
+        if !reached.contains(&node.id) {
+            diagnostics.push(Diagnostic::warning(
+                DiagnosticKind::UnreachableNode,
+                vec![node.id],
+                format!("node {} is not reachable from any trigger", node.id),
+            ));
+        }
+    }
+
+    diagnostics
+}
+
+fn find_cycles(
+    id: Uuid,
+    graph: &HashMap<Uuid, Vec<Uuid>>,
+    visited: &mut HashSet<Uuid>,
+    path: &mut Vec<Uuid>,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    if let Some(start) = path.iter().position(|node| *node == id) {
+        let nodes = path[start..].to_vec();
+        diagnostics.push(Diagnostic::error(
+            DiagnosticKind::Cycle,
+            nodes.clone(),
+            format!(
+                "links form a cycle through {}, set allow_cycle on a link to allow it",
+                nodes
+                    .iter()
+                    .map(Uuid::to_string)
+                    .collect::<Vec<_>>()
+                    .join(" -> ")
+            ),
+        ));
+        return;
+    }
+    if !visited.insert(id) {
+        return;
+    }
+
+    path.push(id);
+    for next in graph.get(&id).into_iter().flatten() {
+        find_cycles(*next, graph, visited, path, diagnostics);
+    }
+    path.pop();
+}
@@ -0,0 +1,201 @@
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    thread,
+    time::{Duration, Instant},
+};
+
+use crossbeam_channel::{Receiver, RecvTimeoutError, Sender};
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{
+    nodes::trigger::{FileEvent, Trigger},
+    workflow::Workflow,
+};
+
+/// Files changed under a `Trigger::FileWatch` node, frontends pass it to
+/// `Executor::trigger_node` with `paths` as params.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileWatchEvent {
+    pub workflow_id: Uuid,
+    pub node_id: Uuid,
+    pub paths: Vec<PathBuf>,
+}
+
+pub type FileWatchSender = Sender<FileWatchEvent>;
+pub type FileWatchReceiver = Receiver<FileWatchEvent>;
+
+// (workflow id, node id)
+type WatchKey = (Uuid, Uuid);
+
+/// A path reported by a watch, waiting for its debounce window to pass.
+struct Change {
+    key: WatchKey,
+    path: PathBuf,
+    debounce: Duration,
+}
+
+/// Keeps a file system watch for every `Trigger::FileWatch` of the active workflows.
+pub struct FileWatcher {
+    // watches stop when dropped
+    watches: HashMap<WatchKey, RecommendedWatcher>,
+    changes: Sender<Change>,
+    receiver: FileWatchReceiver,
+}
+
+impl Default for FileWatcher {
+    fn default() -> Self {
+        let (sender, receiver) = crossbeam_channel::unbounded();
+        let (changes, changes_receiver) = crossbeam_channel::unbounded();
+        // stops once the watcher and its watches are dropped
+        thread::spawn(move || debounce(changes_receiver, sender));
+        Self {
+            watches: HashMap::new(),
+            changes,
+            receiver,
+        }
+    }
+}
+
+impl FileWatcher {
+    pub fn receiver(&self) -> &FileWatchReceiver {
+        &self.receiver
+    }
+
+    pub fn add_workflow(&mut self, workflow: &Workflow) {
+        for (node_id, trigger) in workflow.get_file_watches() {
+            let key = (workflow.id, node_id);
+            match self.watch(key, trigger) {
+                Ok(watcher) => {
+                    self.watches.insert(key, watcher);
+                }
+                Err(err) => {
+                    tracing::error!(workflow_id = %workflow.id, %node_id, %err, "watch file failed");
+                }
+            }
+        }
+    }
+
+    pub fn remove_workflow(&mut self, workflow_id: Uuid) {
+        self.watches.retain(|(id, _), _| *id != workflow_id);
+    }
+
+    fn watch(&self, key: WatchKey, trigger: &Trigger) -> crate::result::Result<RecommendedWatcher> {
+        let (path, events, glob, recursive, debounce) = match trigger {
+            Trigger::FileWatch {
+                path,
+                events,
+                glob,
+                recursive,
+                debounce,
+                ..
+            } => (
+                path,
+                events.clone(),
+                glob,
+                *recursive,
+                Duration::from_millis(*debounce),
+            ),
+            _ => return Err(crate::Error::new("trigger is not a FileWatch")),
+        };
+        let pattern = glob.as_deref().map(glob::Pattern::new).transpose()?;
+        let changes = self.changes.clone();
+
+        let mut watcher =
+            notify::recommended_watcher(move |res: notify::Result<notify::Event>| {
+                let event = match res {
+                    Ok(event) => event,
+                    Err(err) => {
+                        tracing::error!(%err, "file watch error");
+                        return;
+                    }
+                };
+                let matched = match event.kind {
+                    EventKind::Create(_) => events.contains(&FileEvent::Create),
+                    EventKind::Modify(_) => events.contains(&FileEvent::Modify),
+                    EventKind::Remove(_) => events.contains(&FileEvent::Remove),
+                    _ => false,
+                };
+                if !matched {
+                    return;
+                }
+                for path in event.paths {
+                    if pattern.iter().all(|p| p.matches_path(&path)) {
+                        changes
+                            .send(Change {
+                                key,
+                                path,
+                                debounce,
+                            })
+                            .ok();
+                    }
+                }
+            })?;
+
+        let mode = if recursive {
+            RecursiveMode::Recursive
+        } else {
+            RecursiveMode::NonRecursive
+        };
+        watcher.watch(&expand_home(path), mode)?;
+        Ok(watcher)
+    }
+}
+
+/// Sends a path once it hasn't changed for the debounce window of its watch,
+/// paths of the same watch settling together are sent as one event.
+fn debounce(changes: Receiver<Change>, sender: FileWatchSender) {
+    // when each changed path settles
+    let mut pending: HashMap<WatchKey, HashMap<PathBuf, Instant>> = HashMap::new();
+    loop {
+        let next = pending.values().flat_map(HashMap::values).min().copied();
+        let change = match next {
+            Some(next) => changes.recv_timeout(next.saturating_duration_since(Instant::now())),
+            None => changes.recv().map_err(|_| RecvTimeoutError::Disconnected),
+        };
+        match change {
+            Ok(Change {
+                key,
+                path,
+                debounce,
+            }) => {
+                let due = Instant::now() + debounce;
+                pending.entry(key).or_default().insert(path, due);
+            }
+            Err(RecvTimeoutError::Timeout) => {}
+            Err(RecvTimeoutError::Disconnected) => return,
+        }
+
+        let now = Instant::now();
+        for ((workflow_id, node_id), paths) in pending.iter_mut() {
+            let mut settled = paths
+                .iter()
+                .filter(|(_, due)| **due <= now)
+                .map(|(path, _)| path.clone())
+                .collect::<Vec<_>>();
+            if settled.is_empty() {
+                continue;
+            }
+            settled.sort();
+            for path in &settled {
+                paths.remove(path);
+            }
+            let event = FileWatchEvent {
+                workflow_id: *workflow_id,
+                node_id: *node_id,
+                paths: settled,
+            };
+            sender.send(event).ok();
+        }
+        pending.retain(|_, paths| !paths.is_empty());
+    }
+}
+
+fn expand_home(path: &str) -> PathBuf {
+    match (path.strip_prefix('~'), directories::BaseDirs::new()) {
+        (Some(rest), Some(dirs)) => dirs.home_dir().join(rest.trim_start_matches(['/', '\\'])),
+        _ => Path::new(path).to_path_buf(),
+    }
+}
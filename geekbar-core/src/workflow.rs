@@ -1,10 +1,11 @@
 use std::collections::HashMap;
 use std::path::Path;
 
-use crate::link::Link;
+use crate::link::{Link, Modifier};
 use crate::node::Node;
 use crate::nodes::trigger::Trigger;
 use crate::params::Params;
+use crate::validator::{self, Diagnostic};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
@@ -17,18 +18,20 @@ pub struct Workflow {
     nodes: HashMap<Uuid, WorkflowNode>,
     // key is node_id, value is links that from this node
     links: HashMap<Uuid, Vec<Link>>,
-    // key is trigger id, value is node id
+    // key is trigger id, value is node id, only triggers that fire by id
     entries: HashMap<String, Uuid>,
-    // triggers
-    triggers: Vec<Trigger>,
+    // triggers and their node ids
+    triggers: Vec<(Uuid, Trigger)>,
+    // problems found while loading, see `validator::validate`
+    diagnostics: Vec<Diagnostic>,
 }
 
 #[derive(Serialize, Deserialize)]
 pub struct WorkflowConfig {
     pub id: Uuid,
     title: String,
-    nodes: Vec<WorkflowNode>,
-    links: Vec<Link>,
+    pub(crate) nodes: Vec<WorkflowNode>,
+    pub(crate) links: Vec<Link>,
 }
 
 impl WorkflowConfig {
@@ -48,6 +51,7 @@ impl Workflow {
     }
 
     pub fn from_config(workflow_config: WorkflowConfig) -> crate::result::Result<Self> {
+        let diagnostics = validator::validate_config(&workflow_config);
         let mut nodes = HashMap::new();
         let mut links = HashMap::<Uuid, Vec<Link>>::new();
         let mut entries = HashMap::<String, Uuid>::new();
@@ -60,8 +64,10 @@ impl Workflow {
                     .as_any()
                     .downcast_ref::<Trigger>()
                     .expect("node is not a Trigger");
-                triggers.push(trigger.to_owned());
-                entries.insert(trigger.id(), node.id);
+                if trigger.fires_by_id() {
+                    entries.insert(trigger.id(), node.id);
+                }
+                triggers.push((node.id, trigger.to_owned()));
             }
             nodes.insert(node.id, node);
         }
@@ -75,9 +81,14 @@ impl Workflow {
             links,
             entries,
             triggers,
+            diagnostics,
         })
     }
 
+    pub fn diagnostics(&self) -> &[Diagnostic] {
+        &self.diagnostics
+    }
+
     pub fn has_trigger(&self, trigger_id: &str) -> bool {
         self.entries.contains_key(trigger_id)
     }
@@ -89,10 +100,37 @@ impl Workflow {
     pub fn get_keywords(&self) -> Vec<&Trigger> {
         self.triggers
             .iter()
+            .map(|(_, trigger)| trigger)
             .filter(|o| matches!(**o, Trigger::Keyword { .. }))
             .collect()
     }
 
+    pub fn get_shortcuts(&self) -> Vec<&Trigger> {
+        self.triggers
+            .iter()
+            .map(|(_, trigger)| trigger)
+            .filter(|o| matches!(**o, Trigger::Shortcut { .. }))
+            .collect()
+    }
+
+    /// Schedules and their node ids.
+    pub fn get_schedules(&self) -> Vec<(Uuid, &Trigger)> {
+        self.triggers
+            .iter()
+            .filter(|(_, o)| matches!(*o, Trigger::Schedule { .. }))
+            .map(|(node_id, trigger)| (*node_id, trigger))
+            .collect()
+    }
+
+    /// File watches and their node ids.
+    pub fn get_file_watches(&self) -> Vec<(Uuid, &Trigger)> {
+        self.triggers
+            .iter()
+            .filter(|(_, o)| matches!(*o, Trigger::FileWatch { .. }))
+            .map(|(node_id, trigger)| (*node_id, trigger))
+            .collect()
+    }
+
     pub fn get_node(&self, node_id: &Uuid) -> Option<&WorkflowNode> {
         self.nodes.get(node_id)
     }
@@ -100,15 +138,26 @@ impl Workflow {
     pub fn next_nodes(
         &self,
         id: &Uuid,
-        _params: &Params,
+        params: &Params,
+        modifiers: &[Modifier],
     ) -> crate::result::Result<Vec<&WorkflowNode>> {
         let links = self.links.get(id);
         match links {
             Some(links) => {
+                // only the links bound to exactly the held modifiers are followed
+                let matched = links
+                    .iter()
+                    .filter(|link| link.matches_modifiers(modifiers));
+
                 let mut nodes = Vec::new();
-                for link in links {
-                    // TODO: link condition filter
-                    let next_node = self.nodes.get(&link.to).unwrap();
+                for link in matched {
+                    if !link.is_satisfied(params)? {
+                        continue;
+                    }
+                    let next_node = self.nodes.get(&link.to).ok_or(crate::Error::DanglingLink {
+                        from: link.from,
+                        to: link.to,
+                    })?;
                     nodes.push(next_node);
                 }
                 Ok(nodes)
@@ -0,0 +1,71 @@
+mod common;
+
+use common::keyword_workflow;
+use geekbar_core::{
+    executor::{CollisionPolicy, Executor},
+    prompt::Prompt,
+    Error,
+};
+use serde_json::json;
+use std::time::Duration;
+
+#[test]
+fn reject_keeps_first_owner() -> anyhow::Result<()> {
+    let mut executor = Executor::with_workers(1);
+    let first = keyword_workflow("test", "test")?;
+    let second = keyword_workflow("test", "test")?;
+    executor.add_workflow(first.clone())?;
+
+    assert!(matches!(
+        executor.add_workflow(second.clone()),
+        Err(Error::TriggerCollision { workflow_id, .. }) if workflow_id == first.id
+    ));
+    assert!(!executor.has_workflow(second.id));
+    assert_eq!(executor.trigger_owners("test"), vec![first.id]);
+    Ok(())
+}
+
+#[test]
+fn removal_only_drops_own_entries() -> anyhow::Result<()> {
+    let mut executor = Executor::with_workers(1);
+    executor.set_collision_policy(CollisionPolicy::LastWins);
+    let first = keyword_workflow("test", "test")?;
+    let second = keyword_workflow("test", "test")?;
+    executor.add_workflow(first.clone())?;
+    executor.add_workflow(second.clone())?;
+    assert_eq!(executor.trigger_owners("test"), vec![first.id, second.id]);
+
+    executor.remove_workflow(second.id)?;
+    assert_eq!(executor.trigger_owners("test"), vec![first.id]);
+    assert!(executor.trigger("test", None::<()>)?);
+    let work = executor.receiver().recv_timeout(Duration::from_secs(5))?;
+    assert_eq!(work.workflow_id(), first.id);
+    Ok(())
+}
+
+#[test]
+fn choose_lets_the_user_pick() -> anyhow::Result<()> {
+    let mut executor = Executor::with_workers(1);
+    executor.set_collision_policy(CollisionPolicy::Choose);
+    let first = keyword_workflow("test", "test")?;
+    let second = keyword_workflow("test", "test")?;
+    executor.add_workflow(first)?;
+    executor.add_workflow(second.clone())?;
+
+    assert!(executor.trigger("test", Some("input"))?);
+    let mut chooser = executor.receiver().recv_timeout(Duration::from_secs(5))?;
+    assert!(chooser.is_chooser());
+    let value = match chooser.params.take_prompt() {
+        Some(Prompt::Select { options, .. }) => {
+            assert_eq!(options.len(), 2);
+            options[1].value.clone()
+        }
+        prompt => panic!("expected a select prompt, got {:?}", prompt),
+    };
+
+    executor.receive_work(chooser, Some(value), &[])?;
+    let work = executor.receiver().recv_timeout(Duration::from_secs(5))?;
+    assert_eq!(work.workflow_id(), second.id);
+    assert_eq!(work.params.get_value(), &json!("input"));
+    Ok(())
+}
@@ -0,0 +1,106 @@
+//! Fixtures shared by the integration tests, not every test uses all of them.
+#![allow(dead_code)]
+
+use geekbar_core::{
+    executor::Executor,
+    node::Node,
+    nodes::list_filter::{ListFilter, ListOption},
+    params::Params,
+    prompt::Prompt,
+    workflow::{Workflow, WorkflowBuilder},
+};
+use serde_json::{json, Value};
+use std::{
+    io::{Read, Write},
+    net::TcpListener,
+    sync::Arc,
+    thread,
+};
+use uuid::Uuid;
+
+pub const TRIGGER_ID: &str = "92bea399-bf55-4eca-982b-3e7fe989b076";
+pub const A_ID: &str = "30e44c7f-c704-4d85-89f4-1f2d1e61ac77";
+pub const B_ID: &str = "4b0306a8-6e9a-42e4-bf8e-3d26aca27106";
+
+pub fn node(id: &str) -> Value {
+    json!({ "id": id, "type": "SetVars", "config": {} })
+}
+
+pub fn trigger(id: &str, keyword: &str) -> Value {
+    titled_trigger(id, keyword, keyword)
+}
+
+pub fn titled_trigger(id: &str, keyword: &str, title: &str) -> Value {
+    json!({
+        "id": id,
+        "type": "Trigger",
+        "config": { "type": "Keyword", "title": title, "keyword": keyword }
+    })
+}
+
+pub fn workflow(nodes: Vec<Value>, links: Value) -> anyhow::Result<Workflow> {
+    let json = json!({ "id": Uuid::new_v4(), "title": "test", "nodes": nodes, "links": links });
+    Ok(WorkflowBuilder::default().json(json.to_string()).build()?)
+}
+
+/// A workflow with only a keyword trigger, its ids are random.
+pub fn keyword_workflow(keyword: &str, title: &str) -> anyhow::Result<Arc<Workflow>> {
+    let trigger = titled_trigger(&Uuid::new_v4().to_string(), keyword, title);
+    Ok(Arc::new(workflow(vec![trigger], json!([]))?))
+}
+
+/// Adds a [`keyword_workflow`] to the executor and returns the workflow id.
+pub fn add_keyword(executor: &mut Executor, keyword: &str, title: &str) -> anyhow::Result<Uuid> {
+    let workflow = keyword_workflow(keyword, title)?;
+    let workflow_id = workflow.id;
+    executor.add_workflow(workflow)?;
+    Ok(workflow_id)
+}
+
+/// Runs a `ListFilter` built from `config` on `value` and returns the options it prompts.
+pub fn list_options(config: Value, value: Value) -> anyhow::Result<Vec<ListOption>> {
+    list_options_with(config, Params::new(value))
+}
+
+/// [`list_options`] with the given params, e.g. to set vars.
+pub fn list_options_with(config: Value, params: Params) -> anyhow::Result<Vec<ListOption>> {
+    let node: ListFilter = serde_json::from_value(config)?;
+    let mut params = node.execute(params)?;
+    match params.take_prompt() {
+        Some(Prompt::Select { options, .. } | Prompt::FuzzySelect { options, .. }) => Ok(options),
+        prompt => panic!("unexpected prompt {:?}", prompt),
+    }
+}
+
+/// Serves one canned response and returns the raw request it got.
+pub fn serve_once(response: &'static str) -> (String, thread::JoinHandle<String>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let handle = thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        let mut request = Vec::new();
+        let mut buf = [0; 4096];
+        // headers and the small test bodies arrive before we answer
+        loop {
+            let n = stream.read(&mut buf).unwrap();
+            request.extend_from_slice(&buf[..n]);
+            let text = String::from_utf8_lossy(&request);
+            if let Some(end) = text.find("\r\n\r\n") {
+                let length = text[..end]
+                    .lines()
+                    .find_map(|line| {
+                        line.to_lowercase()
+                            .strip_prefix("content-length: ")
+                            .map(|l| l.parse::<usize>().unwrap())
+                    })
+                    .unwrap_or(0);
+                if request.len() >= end + 4 + length {
+                    break;
+                }
+            }
+        }
+        stream.write_all(response.as_bytes()).unwrap();
+        String::from_utf8(request).unwrap()
+    });
+    (url, handle)
+}
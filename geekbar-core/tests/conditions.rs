@@ -0,0 +1,107 @@
+use geekbar_core::{link::Condition, params::Params, Error};
+use serde_json::{json, Value};
+
+fn evaluate(condition: Value, params: &Params) -> geekbar_core::Result<bool> {
+    let condition: Condition = serde_json::from_value(condition).unwrap();
+    condition.evaluate(params)
+}
+
+fn value(operator: Value) -> Value {
+    json!({ "type": "Value", "operator": operator })
+}
+
+#[test]
+fn compares_values_loosely() -> anyhow::Result<()> {
+    let params = Params::new(json!("10"));
+    assert!(evaluate(value(json!({ "Eq": 10 })), &params)?);
+    assert!(evaluate(value(json!({ "Gt": 9 })), &params)?);
+    // numeric strings compare as numbers, not "10" < "9"
+    assert!(evaluate(value(json!({ "Gt": "9" })), &params)?);
+    assert!(evaluate(value(json!({ "Le": 10 })), &params)?);
+    assert!(!evaluate(value(json!({ "Lt": 10 })), &params)?);
+    assert!(evaluate(value(json!({ "Between": [1, 10] })), &params)?);
+    assert!(evaluate(value(json!({ "In": [1, "10"] })), &params)?);
+    assert!(evaluate(value(json!({ "Match": "^\\d+$" })), &params)?);
+    assert!(evaluate(value(json!("NonNull")), &params)?);
+
+    let params = Params::new(json!(["a", "b"]));
+    assert!(evaluate(value(json!({ "Includes": "b" })), &params)?);
+    assert!(!evaluate(value(json!({ "Includes": "c" })), &params)?);
+    Ok(())
+}
+
+#[test]
+fn resolves_subjects_and_templates() -> anyhow::Result<()> {
+    let mut params = Params::new(json!({ "items": [{ "name": "geekbar" }] }));
+    params.set_var("limit", json!(3));
+
+    let condition = json!({
+        "type": "Value",
+        "subject": { "type": "Value", "path": "items.0.name" },
+        "operator": { "Eq": "geekbar" }
+    });
+    assert!(evaluate(condition, &params)?);
+
+    // operands are rendered, missing paths are null
+    let condition = json!({
+        "type": "Value",
+        "subject": { "type": "Var", "name": "limit" },
+        "operator": { "Ge": "{{limit}}" }
+    });
+    assert!(evaluate(condition, &params)?);
+    let condition = json!({
+        "type": "Value",
+        "subject": { "type": "Var", "name": "limit", "path": "missing" },
+        "operator": "Nullable"
+    });
+    assert!(evaluate(condition, &params)?);
+    Ok(())
+}
+
+#[test]
+fn combines_conditions() -> anyhow::Result<()> {
+    let params = Params::new(json!(5));
+    let yes = value(json!({ "Gt": 1 }));
+    let no = value(json!({ "Lt": 1 }));
+
+    assert!(evaluate(
+        json!({ "type": "And", "conditions": [yes, yes] }),
+        &params
+    )?);
+    assert!(!evaluate(
+        json!({ "type": "And", "conditions": [yes, no] }),
+        &params
+    )?);
+    assert!(evaluate(
+        json!({ "type": "Or", "conditions": [no, yes] }),
+        &params
+    )?);
+    assert!(!evaluate(
+        json!({ "type": "Or", "conditions": [no] }),
+        &params
+    )?);
+    // empty groups impose no constraint
+    assert!(evaluate(
+        json!({ "type": "And", "conditions": [] }),
+        &params
+    )?);
+    assert!(evaluate(
+        json!({ "type": "Or", "conditions": [] }),
+        &params
+    )?);
+    Ok(())
+}
+
+#[test]
+fn reports_invalid_operands() {
+    let params = Params::new(json!(5));
+    assert!(matches!(
+        evaluate(value(json!({ "Between": [1] })), &params),
+        Err(Error::ConditionError(_))
+    ));
+    assert!(matches!(
+        evaluate(value(json!({ "Match": 1 })), &params),
+        Err(Error::ConditionError(_))
+    ));
+    assert!(evaluate(value(json!({ "Match": "(" })), &params).is_err());
+}
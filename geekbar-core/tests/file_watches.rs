@@ -0,0 +1,61 @@
+mod common;
+
+use common::workflow;
+use geekbar_core::{executor::Executor, workflow::Workflow};
+use serde_json::json;
+use std::{collections::HashSet, io::Write, sync::Arc, time::Duration};
+
+fn watch_workflow(path: &std::path::Path) -> anyhow::Result<Arc<Workflow>> {
+    let trigger = json!({
+        "id": uuid::Uuid::new_v4(),
+        "type": "Trigger",
+        "config": { "type": "FileWatch", "path": path, "glob": "*.txt", "debounce": 200 }
+    });
+    Ok(Arc::new(workflow(vec![trigger], json!([]))?))
+}
+
+#[test]
+fn same_path_in_two_workflows_fires_once_each() -> anyhow::Result<()> {
+    let dir = std::env::temp_dir().join(format!("geekbar-watch-{}", uuid::Uuid::new_v4()));
+    std::fs::create_dir_all(&dir)?;
+
+    let mut executor = Executor::with_workers(1);
+    let first = watch_workflow(&dir)?;
+    let second = watch_workflow(&dir)?;
+    executor.add_workflow(first.clone())?;
+    executor.add_workflow(second.clone())?;
+    assert!(executor
+        .trigger_owners(&format!("@watch {} *.txt", dir.display()))
+        .is_empty());
+
+    // creating and writing in several steps is one change
+    let path = dir.join("note.txt");
+    let mut file = std::fs::File::create(&path)?;
+    for line in ["a", "b", "c"] {
+        writeln!(file, "{}", line)?;
+        file.flush()?;
+        std::thread::sleep(Duration::from_millis(20));
+    }
+    drop(file);
+
+    let mut fired = HashSet::new();
+    for _ in 0..2 {
+        let event = executor
+            .file_watch_receiver()
+            .recv_timeout(Duration::from_secs(5))?;
+        assert_eq!(event.paths, vec![path.clone()]);
+        assert!(fired.insert(event.workflow_id));
+        assert!(executor.trigger_node(event.workflow_id, event.node_id, Some(event.paths))?);
+    }
+    assert_eq!(fired, HashSet::from([first.id, second.id]));
+    assert!(executor
+        .file_watch_receiver()
+        .recv_timeout(Duration::from_millis(500))
+        .is_err());
+
+    let work = executor.receiver().recv_timeout(Duration::from_secs(5))?;
+    assert_eq!(work.params.get_value(), &json!([path]));
+
+    std::fs::remove_dir_all(&dir)?;
+    Ok(())
+}
@@ -0,0 +1,83 @@
+mod common;
+
+use common::add_keyword;
+use geekbar_core::{
+    executor::{Executor, MatchKind},
+    frecency::{Frecency, PickScope},
+};
+use uuid::Uuid;
+
+#[test]
+fn picked_keywords_rank_first() -> anyhow::Result<()> {
+    let mut executor = Executor::with_workers(1);
+    add_keyword(&mut executor, "se", "se")?;
+    add_keyword(&mut executor, "sea", "sea")?;
+    let search_id = add_keyword(&mut executor, "search", "search")?;
+
+    let ranked = |executor: &Executor, input: &str| {
+        executor
+            .match_keywords(input)
+            .into_iter()
+            .map(|candidate| candidate.trigger_id)
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(ranked(&executor, "s"), vec!["se", "sea", "search"]);
+
+    assert!(executor.record_keyword("search", "s"));
+    assert!(!executor.record_keyword("missing", "s"));
+    assert_eq!(ranked(&executor, "s"), vec!["search", "se", "sea"]);
+    // a longer query still counts the pick, exact matches stay first
+    assert_eq!(ranked(&executor, "se"), vec!["se", "search", "sea"]);
+    assert_eq!(executor.match_keywords("se")[0].kind, MatchKind::Exact);
+
+    assert!(executor.reset_frecency(search_id));
+    assert_eq!(ranked(&executor, "s"), vec!["se", "sea", "search"]);
+    Ok(())
+}
+
+#[test]
+fn strong_match_beats_frequent_weak_one() -> anyhow::Result<()> {
+    let mut executor = Executor::with_workers(1);
+    add_keyword(&mut executor, "atxb", "atxb")?;
+    add_keyword(&mut executor, "aa-tb", "aa-tb")?;
+    add_keyword(&mut executor, "ab-tb", "ab-tb")?;
+
+    let ranked = |executor: &Executor| {
+        executor
+            .match_keywords("tb")
+            .into_iter()
+            .map(|candidate| candidate.trigger_id)
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(ranked(&executor), vec!["aa-tb", "ab-tb", "atxb"]);
+
+    for _ in 0..5 {
+        assert!(executor.record_keyword("atxb", "tb"));
+    }
+    assert!(executor.record_keyword("ab-tb", "tb"));
+    // picks only reorder equally good matches
+    assert_eq!(ranked(&executor), vec!["ab-tb", "aa-tb", "atxb"]);
+    Ok(())
+}
+
+#[test]
+fn recent_picks_weigh_more() {
+    let workflow_id = Uuid::new_v4();
+    let node_id = Uuid::new_v4();
+    let scope = PickScope::Node(node_id);
+    let now = 100 * 24 * 60 * 60;
+
+    let mut frecency = Frecency::default();
+    frecency.record(workflow_id, scope, "Ab", "\"old\"", now - 60 * 24 * 60 * 60);
+    frecency.record(workflow_id, scope, "ab", "\"old\"", now - 60 * 24 * 60 * 60);
+    frecency.record(workflow_id, scope, "a", "\"new\"", now);
+
+    let old = frecency.score(workflow_id, scope, "a", "\"old\"", now);
+    let new = frecency.score(workflow_id, scope, "a", "\"new\"", now);
+    assert!(new > old && old > 0.0);
+    assert_eq!(frecency.score(workflow_id, scope, "b", "\"new\"", now), 0.0);
+    assert_eq!(
+        frecency.score(workflow_id, PickScope::Trigger, "a", "\"new\"", now),
+        0.0
+    );
+}
@@ -0,0 +1,92 @@
+mod common;
+
+use common::list_options;
+use geekbar_core::{
+    nodes::list_filter::Highlights,
+    utils::{FuzzyMatcher, Matcher, SubstringMatcher},
+};
+use serde_json::json;
+
+#[test]
+fn scores_and_positions() {
+    let matcher = FuzzyMatcher::default();
+    let m = matcher.find("Open Terminal", "ot").unwrap();
+    assert_eq!(m.positions, vec![0, 5]);
+    assert_eq!(m.ranges(), vec![(0, 1), (5, 6)]);
+
+    // word starts and consecutive chars beat scattered ones
+    let boundary = matcher.find("git-checkout", "gc").unwrap().score;
+    let scattered = matcher.find("magic", "gc").unwrap().score;
+    assert!(boundary > scattered);
+    let consecutive = matcher.find("xxterm", "term").unwrap().score;
+    let spread = matcher.find("txexrxm", "term").unwrap().score;
+    assert!(consecutive > spread);
+
+    assert!(matcher.find("abc", "abd").is_none());
+    assert_eq!(
+        matcher.find("abc", "").unwrap().positions,
+        Vec::<usize>::new()
+    );
+}
+
+#[test]
+fn matches_pinyin() {
+    let matcher = FuzzyMatcher::default();
+    assert_eq!(
+        matcher.find("百度一下", "bd").unwrap().ranges(),
+        vec![(0, 2)]
+    );
+    assert_eq!(
+        matcher.find("打开百度", "baidu").unwrap().ranges(),
+        vec![(2, 4)]
+    );
+    assert!(FuzzyMatcher { pinyin: false }.find("百度", "bd").is_none());
+
+    let m = SubstringMatcher.find("Hello World", "o w").unwrap();
+    assert_eq!(m.ranges(), vec![(4, 7)]);
+    assert!(SubstringMatcher.find("Hello World", "hw").is_none());
+}
+
+#[test]
+fn ranks_list_options() -> anyhow::Result<()> {
+    let config = json!({
+        "title": "sites",
+        "options": [
+            { "title": "Google Translate", "value": 1, "description": null },
+            { "title": "GitHub", "value": 2, "description": "code hosting" },
+            { "title": "Baidu", "value": 3, "description": "百度搜索" },
+            { "title": "Gmail", "value": 4, "description": null }
+        ]
+    });
+
+    let ranked = |search: &str| -> anyhow::Result<Vec<(String, Option<Highlights>)>> {
+        Ok(list_options(config.clone(), json!(search))?
+            .into_iter()
+            .map(|option| (option.title, option.highlights))
+            .collect())
+    };
+    let highlights = |title: Vec<(usize, usize)>, description: Vec<(usize, usize)>| {
+        Some(Highlights { title, description })
+    };
+
+    assert_eq!(
+        ranked("gh")?,
+        vec![(
+            "GitHub".to_string(),
+            highlights(vec![(0, 1), (3, 4)], vec![])
+        )]
+    );
+    // both only match by their descriptions, "bai du sou suo" at word starts ranks higher
+    assert_eq!(
+        ranked("ds")?,
+        vec![
+            ("Baidu".to_string(), highlights(vec![], vec![(1, 3)])),
+            (
+                "GitHub".to_string(),
+                highlights(vec![], vec![(2, 3), (7, 8)])
+            ),
+        ]
+    );
+    assert_eq!(ranked("")?.len(), 4);
+    Ok(())
+}
@@ -0,0 +1,75 @@
+mod common;
+
+use common::workflow;
+use geekbar_core::{
+    hotkey::{HotkeyEvent, HotkeyRegistry},
+    workflow::Workflow,
+};
+use serde_json::json;
+use uuid::Uuid;
+
+fn shortcut_workflow(keys: &[&str]) -> anyhow::Result<Workflow> {
+    let trigger = json!({
+        "id": Uuid::new_v4(),
+        "type": "Trigger",
+        "config": { "type": "Shortcut", "keys": keys, "title": "shortcut" }
+    });
+    workflow(vec![trigger], json!([]))
+}
+
+/// Drains the sent events as (registered, trigger id, owner).
+fn events(registry: &HotkeyRegistry) -> Vec<(bool, String, Uuid)> {
+    registry
+        .receiver()
+        .try_iter()
+        .map(|event| match event {
+            HotkeyEvent::Register(hotkey) => (true, hotkey.id, hotkey.workflow_id),
+            HotkeyEvent::Unregister(hotkey) => (false, hotkey.id, hotkey.workflow_id),
+        })
+        .collect()
+}
+
+#[test]
+fn sends_register_and_unregister_events() -> anyhow::Result<()> {
+    let mut registry = HotkeyRegistry::default();
+    let first = shortcut_workflow(&["K", "Ctrl"])?;
+    let second = shortcut_workflow(&["Ctrl", "K"])?;
+    let other = shortcut_workflow(&["Alt", "J"])?;
+
+    registry.add_workflow(&first);
+    registry.add_workflow(&other);
+    assert_eq!(
+        events(&registry),
+        vec![
+            (true, "Ctrl+K".to_string(), first.id),
+            (true, "Alt+J".to_string(), other.id),
+        ]
+    );
+
+    // the later workflow takes the shortcut over
+    registry.add_workflow(&second);
+    assert_eq!(
+        events(&registry),
+        vec![
+            (false, "Ctrl+K".to_string(), first.id),
+            (true, "Ctrl+K".to_string(), second.id),
+        ]
+    );
+    assert_eq!(
+        registry.get("Ctrl+K").map(|h| h.workflow_id),
+        Some(second.id)
+    );
+
+    // removing the previous owner leaves the shortcut of the new one alone
+    registry.remove_workflow(first.id);
+    assert!(events(&registry).is_empty());
+    assert!(registry.get("Ctrl+K").is_some());
+
+    registry.remove_workflow(second.id);
+    assert_eq!(
+        events(&registry),
+        vec![(false, "Ctrl+K".to_string(), second.id)]
+    );
+    assert_eq!(registry.hotkeys().len(), 1);
+    Ok(())
+}
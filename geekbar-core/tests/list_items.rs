@@ -0,0 +1,63 @@
+mod common;
+
+use common::list_options;
+use serde_json::json;
+
+fn options(
+    config: serde_json::Value,
+    value: serde_json::Value,
+) -> anyhow::Result<Vec<(String, serde_json::Value, Option<String>)>> {
+    Ok(list_options(config, value)?
+        .into_iter()
+        .map(|option| (option.title, option.value, option.description))
+        .collect())
+}
+
+#[test]
+fn options_from_value() -> anyhow::Result<()> {
+    let config = json!({
+        "title": "repos",
+        "items": {
+            "path": "items",
+            "title": "{{index}}. {{item.name}}",
+            "value": "{{item.url}}",
+            "description": "{{item.stars}} stars"
+        }
+    });
+    let value = json!({
+        "items": [
+            { "name": "geekbar", "url": "https://a", "stars": 3 },
+            { "name": "{{raw}}", "url": "https://b", "stars": 5 }
+        ]
+    });
+    assert_eq!(
+        options(config, value)?,
+        vec![
+            (
+                "0. geekbar".to_string(),
+                json!("https://a"),
+                Some("3 stars".to_string())
+            ),
+            (
+                "1. {{raw}}".to_string(),
+                json!("https://b"),
+                Some("5 stars".to_string())
+            ),
+        ]
+    );
+
+    // the whole item is the value by default
+    let config = json!({ "items": { "title": "{{item}}" } });
+    assert_eq!(
+        options(config.clone(), json!(["a", "b"]))?,
+        vec![
+            ("a".to_string(), json!("a"), None),
+            ("b".to_string(), json!("b"), None),
+        ]
+    );
+
+    let options = options(config, json!({ "not": "an array" }))?;
+    assert_eq!(options.len(), 1);
+    assert!(options[0].0.contains("not an array"));
+    Ok(())
+}
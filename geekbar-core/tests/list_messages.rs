@@ -0,0 +1,91 @@
+mod common;
+
+use common::list_options_with;
+use geekbar_core::{nodes::list_filter::ListOptionMark, params::Params};
+use serde_json::json;
+
+fn titles_in(
+    config: serde_json::Value,
+    search: &str,
+    locale: Option<&str>,
+) -> anyhow::Result<Vec<(String, bool)>> {
+    let mut params = Params::new(json!(search));
+    if let Some(locale) = locale {
+        params.set_var("locale", json!(locale));
+    }
+    Ok(list_options_with(config, params)?
+        .into_iter()
+        .map(|option| (option.title, option.mark.is_some()))
+        .collect())
+}
+
+fn titles(config: serde_json::Value, search: &str) -> anyhow::Result<Vec<(String, bool)>> {
+    titles_in(config, search, None)
+}
+
+#[test]
+fn configured_messages() -> anyhow::Result<()> {
+    let config = json!({
+        "options": [{ "title": "apple", "value": 1, "description": null }],
+        "need_args": "Always",
+        "placeholder": { "title": "Type a fruit" },
+        "empty": { "title": "No fruit like {{query}}", "mark": "Error" },
+        "hint": { "title": "Enter to pick", "description": "{{query}}" }
+    });
+
+    assert_eq!(
+        titles(config.clone(), "")?,
+        vec![("Type a fruit".to_string(), true)]
+    );
+    assert_eq!(
+        titles(config.clone(), "ap")?,
+        vec![
+            ("apple".to_string(), false),
+            ("Enter to pick".to_string(), true)
+        ]
+    );
+
+    let config = json!({
+        "options": [{ "title": "apple", "value": 1, "description": null }],
+        "empty": { "title": "No fruit like {{query}}", "mark": "Error" }
+    });
+    assert_eq!(
+        titles(config, "kiwi")?,
+        vec![("No fruit like kiwi".to_string(), true)]
+    );
+
+    // nothing configured keeps the list empty
+    assert!(titles(json!({ "options": [] }), "kiwi")?.is_empty());
+    Ok(())
+}
+
+#[test]
+fn localized_placeholder() -> anyhow::Result<()> {
+    let config = json!({ "need_args": "Always" });
+    assert_eq!(
+        titles_in(config.clone(), "", Some("zh-CN"))?,
+        vec![("请输入关键字".to_string(), true)]
+    );
+    assert_eq!(
+        titles_in(config, "", Some("en-US"))?,
+        vec![("Please input keyword".to_string(), true)]
+    );
+
+    let config = json!({
+        "need_args": "Always",
+        "placeholder": {
+            "title": "{{#if (eq (locale) \"zh-CN\")}}输入水果{{else}}Type a fruit{{/if}}",
+            "mark": "Hint"
+        }
+    });
+    assert_eq!(
+        titles_in(config.clone(), "", Some("zh-CN"))?,
+        vec![("输入水果".to_string(), true)]
+    );
+    assert_eq!(
+        titles_in(config, "", Some("en-US"))?,
+        vec![("Type a fruit".to_string(), true)]
+    );
+    assert!(matches!(ListOptionMark::default(), ListOptionMark::Hint));
+    Ok(())
+}
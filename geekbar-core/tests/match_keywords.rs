@@ -0,0 +1,61 @@
+mod common;
+
+use common::add_keyword;
+use geekbar_core::executor::{Executor, MatchKind};
+
+#[test]
+fn ranks_candidates() -> anyhow::Result<()> {
+    let mut executor = Executor::with_workers(1);
+    add_keyword(&mut executor, "search", "search engine")?;
+    add_keyword(&mut executor, "sea", "sea")?;
+    add_keyword(&mut executor, "translate", "翻译")?;
+    add_keyword(&mut executor, "sxa", "s x a")?;
+
+    let candidates = executor
+        .match_keywords("sea")
+        .into_iter()
+        .map(|candidate| (candidate.trigger_id, candidate.kind))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        candidates,
+        vec![
+            ("sea".to_string(), MatchKind::Exact),
+            ("search".to_string(), MatchKind::Prefix),
+        ]
+    );
+
+    let candidates = executor.match_keywords("xa");
+    assert_eq!(candidates[0].trigger_id, "sxa");
+    assert_eq!(candidates[0].kind, MatchKind::Fuzzy);
+
+    let candidates = executor.match_keywords("fy");
+    assert_eq!(candidates.len(), 1);
+    assert_eq!(candidates[0].trigger_id, "translate");
+    assert_eq!(candidates[0].kind, MatchKind::Title);
+    assert_eq!(candidates[0].title, "翻译");
+
+    assert!(executor.match_keywords(" ").is_empty());
+    Ok(())
+}
+
+#[test]
+fn ranks_fuzzy_candidates_by_score() -> anyhow::Result<()> {
+    let mut executor = Executor::with_workers(1);
+    add_keyword(&mut executor, "atxb", "atxb")?;
+    add_keyword(&mut executor, "aa-tb", "aa-tb")?;
+
+    // the word boundary and consecutive chars beat the shorter keyword
+    let candidates = executor
+        .match_keywords("tb")
+        .into_iter()
+        .map(|candidate| (candidate.trigger_id, candidate.kind))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        candidates,
+        vec![
+            ("aa-tb".to_string(), MatchKind::Fuzzy),
+            ("atxb".to_string(), MatchKind::Fuzzy),
+        ]
+    );
+    Ok(())
+}
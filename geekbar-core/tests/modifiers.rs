@@ -0,0 +1,31 @@
+mod common;
+
+use common::{node, trigger, workflow, A_ID, B_ID, TRIGGER_ID};
+use geekbar_core::{link::Modifier, params::Params};
+use serde_json::json;
+
+#[test]
+fn follows_links_of_held_modifiers() -> anyhow::Result<()> {
+    let workflow = workflow(
+        vec![trigger(TRIGGER_ID, "kw"), node(A_ID), node(B_ID)],
+        json!([
+            { "from": TRIGGER_ID, "to": A_ID },
+            { "from": TRIGGER_ID, "to": B_ID, "modifiers": ["Meta"] }
+        ]),
+    )?;
+    let params = Params::new(json!(null));
+    let next = |modifiers: &[Modifier]| -> anyhow::Result<Vec<String>> {
+        Ok(workflow
+            .next_nodes(&TRIGGER_ID.parse()?, &params, modifiers)?
+            .into_iter()
+            .map(|node| node.id.to_string())
+            .collect())
+    };
+
+    assert_eq!(next(&[])?, vec![A_ID]);
+    assert_eq!(next(&[Modifier::Meta])?, vec![B_ID]);
+    // a modifier without links doesn't fall back to the default action
+    assert!(next(&[Modifier::Ctrl])?.is_empty());
+    assert!(next(&[Modifier::Meta, Modifier::Shift])?.is_empty());
+    Ok(())
+}
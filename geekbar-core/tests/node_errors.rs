@@ -0,0 +1,42 @@
+mod common;
+
+use common::{trigger, workflow, A_ID, TRIGGER_ID};
+use geekbar_core::{executor::Executor, nodes::list_filter::ListOptionMark, prompt::Prompt};
+use serde_json::json;
+use std::{sync::Arc, time::Duration};
+
+#[test]
+fn failed_node_sends_error_option() -> anyhow::Result<()> {
+    let failing = json!({
+        "id": A_ID,
+        "type": "RunScript",
+        "config": { "script": "echo broken >&2; exit 3" }
+    });
+    let workflow = workflow(
+        vec![trigger(TRIGGER_ID, "fail"), failing],
+        json!([{ "from": TRIGGER_ID, "to": A_ID }]),
+    )?;
+    let mut executor = Executor::with_workers(1);
+    executor.add_workflow(Arc::new(workflow))?;
+
+    assert!(executor.trigger("fail", None::<()>)?);
+    let work = executor.receiver().recv_timeout(Duration::from_secs(5))?;
+    let run_id = work.run_id();
+    executor.receive_work(work, None, &[])?;
+
+    let mut work = executor.receiver().recv_timeout(Duration::from_secs(5))?;
+    assert_eq!(work.run_id(), run_id);
+    assert_eq!(work.node_id().to_string(), A_ID);
+    let options = match work.params.take_prompt() {
+        Some(Prompt::Select { options, .. }) => options,
+        prompt => panic!("unexpected prompt {:?}", prompt),
+    };
+    assert_eq!(options.len(), 1);
+    assert!(matches!(options[0].mark, Some(ListOptionMark::Error)));
+    assert!(options[0].title.contains("broken"));
+
+    // the run is over, sending the error back does nothing
+    assert!(!executor.cancel(run_id));
+    executor.receive_work(work, None, &[])?;
+    Ok(())
+}
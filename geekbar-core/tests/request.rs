@@ -0,0 +1,59 @@
+mod common;
+
+use common::serve_once;
+use geekbar_core::{node::Node, nodes::request::Request, params::Params, Error};
+use serde_json::json;
+
+fn request(config: serde_json::Value) -> Request {
+    serde_json::from_value(config).unwrap()
+}
+
+#[test]
+fn sends_form_with_query_and_auth() -> anyhow::Result<()> {
+    let (url, server) = serve_once("HTTP/1.1 200 OK\r\nContent-Length: 5\r\n\r\nhello");
+    let node = request(json!({
+        "url": url,
+        "method": "Post",
+        "content_type": "Form",
+        "body": { "q": "{{query}}" },
+        "query": { "page": "2" },
+        "auth": { "type": "Bearer", "token": "secret" },
+        "response": { "type": "Text" }
+    }));
+
+    let params = node.execute(Params::new(json!("a b")))?;
+    assert_eq!(params.get_value(), &json!("hello"));
+
+    let sent = server.join().unwrap();
+    assert!(sent.starts_with("POST /?page=2 "));
+    assert!(sent.contains("content-type: application/x-www-form-urlencoded"));
+    assert!(sent.contains("authorization: Bearer secret"));
+    assert!(sent.ends_with("q=a+b"));
+    Ok(())
+}
+
+#[test]
+fn handles_error_status() -> anyhow::Result<()> {
+    const NOT_FOUND: &str = "HTTP/1.1 404 Not Found\r\nContent-Length: 7\r\n\r\nmissing";
+
+    let (url, server) = serve_once(NOT_FOUND);
+    let err = request(json!({ "url": url }))
+        .execute(Params::new(json!(null)))
+        .map(|_| ())
+        .unwrap_err();
+    assert!(matches!(err, Error::HttpStatus { status: 404, ref body } if body == "missing"));
+    server.join().unwrap();
+
+    let (url, server) = serve_once(NOT_FOUND);
+    let params = request(json!({
+        "url": url,
+        "response": { "type": "Full" },
+        "status_policy": { "type": "Var" }
+    }))
+    .execute(Params::new(json!(null)))?;
+    assert_eq!(params.get_var("status"), Some(&json!(404)));
+    assert_eq!(params.get_value()["body"], json!("missing"));
+    assert_eq!(params.get_value()["headers"]["content-length"], json!("7"));
+    server.join().unwrap();
+    Ok(())
+}
@@ -0,0 +1,100 @@
+mod common;
+
+use common::serve_once;
+use geekbar_core::{
+    cancel::CancellationToken,
+    node::Node,
+    nodes::{
+        list_filter::{ListOption, ListOptionMark},
+        request_filter::RequestFilter,
+    },
+    params::Params,
+    prompt::Prompt,
+    Error,
+};
+use serde_json::json;
+use std::{
+    net::TcpListener,
+    time::{Duration, Instant},
+};
+
+fn fetch(config: serde_json::Value, params: Params) -> geekbar_core::Result<Params> {
+    let node: RequestFilter = serde_json::from_value(config).unwrap();
+    node.execute(params)
+}
+
+fn options(mut params: Params) -> Vec<ListOption> {
+    match params.take_prompt() {
+        Some(Prompt::Select { options, .. }) => options,
+        prompt => panic!("unexpected prompt {:?}", prompt),
+    }
+}
+
+#[test]
+fn maps_items_to_options() -> anyhow::Result<()> {
+    const ITEMS: &str = "HTTP/1.1 200 OK\r\nContent-Length: 82\r\n\r\n\
+        {\"data\":{\"items\":[{\"name\":\"a\",\"id\":1,\"desc\":\"x\"},{\"name\":\"b\",\"id\":2,\"desc\":\"y\"}]}}";
+    let (url, server) = serve_once(ITEMS);
+    let params = fetch(
+        json!({
+            "url": url,
+            "items_path": "data.items",
+            "title_path": "name",
+            "value_path": "id",
+            "description_path": "desc",
+            "status_policy": { "type": "Var", "name": "status" }
+        }),
+        Params::new(json!("q")),
+    )?;
+    server.join().unwrap();
+
+    assert_eq!(params.get_var("status"), Some(&json!(200)));
+    let options = options(params)
+        .into_iter()
+        .map(|option| (option.title, option.value, option.description))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        options,
+        vec![
+            ("a".to_string(), json!(1), Some("x".to_string())),
+            ("b".to_string(), json!(2), Some("y".to_string())),
+        ]
+    );
+    Ok(())
+}
+
+#[test]
+fn error_status_is_an_error_option() -> anyhow::Result<()> {
+    let (url, server) =
+        serve_once("HTTP/1.1 500 Internal Server Error\r\nContent-Length: 4\r\n\r\noops");
+    let params = fetch(json!({ "url": url }), Params::new(json!(null)))?;
+    server.join().unwrap();
+
+    let options = options(params);
+    assert_eq!(options.len(), 1);
+    assert!(matches!(options[0].mark, Some(ListOptionMark::Error)));
+    assert!(options[0].title.contains("oops"));
+    Ok(())
+}
+
+#[test]
+fn cancellation_is_not_an_option() -> anyhow::Result<()> {
+    // accepts the request and never answers
+    let listener = TcpListener::bind("127.0.0.1:0")?;
+    let url = format!("http://{}", listener.local_addr()?);
+
+    let cancellation = CancellationToken::default();
+    let mut params = Params::new(json!(null));
+    params.set_cancellation(cancellation.clone());
+    std::thread::spawn(move || {
+        std::thread::sleep(Duration::from_millis(200));
+        cancellation.cancel();
+    });
+
+    let start = Instant::now();
+    let result = fetch(json!({ "url": url }), params);
+    assert!(matches!(result, Err(Error::Cancelled)));
+    assert!(start.elapsed() < Duration::from_secs(10));
+    drop(listener);
+    Ok(())
+}
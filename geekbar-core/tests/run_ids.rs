@@ -0,0 +1,71 @@
+mod common;
+
+use common::{node, trigger, workflow, A_ID, TRIGGER_ID};
+use geekbar_core::executor::Executor;
+use serde_json::json;
+use std::{collections::HashSet, sync::Arc, time::Duration};
+use uuid::Uuid;
+
+fn executor() -> anyhow::Result<Executor> {
+    let workflow = workflow(
+        vec![trigger(TRIGGER_ID, "kw"), node(A_ID)],
+        json!([{ "from": TRIGGER_ID, "to": A_ID }]),
+    )?;
+    let mut executor = Executor::with_workers(1);
+    executor.add_workflow(Arc::new(workflow))?;
+    Ok(executor)
+}
+
+#[test]
+fn each_trigger_starts_a_new_run() -> anyhow::Result<()> {
+    let executor = executor()?;
+    let mut run_ids = HashSet::new();
+    for _ in 0..3 {
+        assert!(executor.trigger("kw", None::<()>)?);
+        let work = executor.receiver().recv_timeout(Duration::from_secs(5))?;
+        assert!(!work.run_id().is_nil());
+        assert!(run_ids.insert(work.run_id()));
+    }
+    Ok(())
+}
+
+#[test]
+fn works_of_finished_runs_are_ignored() -> anyhow::Result<()> {
+    let executor = executor()?;
+    assert!(executor.trigger("kw", None::<()>)?);
+    let work = executor.receiver().recv_timeout(Duration::from_secs(5))?;
+    let workflow_pid = work.workflow_pid();
+    let stale = work.clone();
+    executor.receive_work(work, None, &[])?;
+
+    // the last node sent back finishes the run
+    let work = executor.receiver().recv_timeout(Duration::from_secs(5))?;
+    assert_eq!(work.node_id().to_string(), A_ID);
+    executor.receive_work(work, None, &[])?;
+    assert_eq!(executor.running_runs(workflow_pid), 0);
+
+    // sending the trigger work again doesn't start its next node
+    executor.receive_work(stale, None, &[])?;
+    assert!(executor
+        .receiver()
+        .recv_timeout(Duration::from_millis(300))
+        .is_err());
+    assert_eq!(executor.running_runs(workflow_pid), 0);
+    Ok(())
+}
+
+#[test]
+fn cancelling_unknown_run_does_nothing() -> anyhow::Result<()> {
+    let executor = executor()?;
+    assert!(executor.trigger("kw", None::<()>)?);
+    let work = executor.receiver().recv_timeout(Duration::from_secs(5))?;
+
+    assert!(!executor.cancel(Uuid::new_v4()));
+    assert_eq!(executor.running_runs(work.workflow_pid()), 1);
+
+    // the run still goes on
+    executor.receive_work(work, None, &[])?;
+    let work = executor.receiver().recv_timeout(Duration::from_secs(5))?;
+    assert_eq!(work.node_id().to_string(), A_ID);
+    Ok(())
+}
@@ -0,0 +1,77 @@
+use geekbar_core::{
+    cancel::CancellationToken, node::Node, nodes::run_script::RunScript, params::Params, Error,
+};
+use serde_json::json;
+use std::time::{Duration, Instant};
+
+fn run_script(config: serde_json::Value, params: Params) -> geekbar_core::Result<Params> {
+    let node: RunScript = serde_json::from_value(config).unwrap();
+    node.execute(params)
+}
+
+/// Whether the process has exited, killed processes may linger as zombies until reaped.
+#[cfg(target_os = "linux")]
+fn exited(pid: &str) -> bool {
+    match std::fs::read_to_string(format!("/proc/{}/stat", pid)) {
+        Ok(stat) => {
+            matches!(stat.rsplit(')').next(), Some(rest) if rest.trim_start().starts_with('Z'))
+        }
+        Err(_) => true,
+    }
+}
+
+#[test]
+fn writes_value_to_stdin() -> anyhow::Result<()> {
+    let config = json!({ "script": "tr a-z A-Z", "stdin": true });
+    let params = run_script(config, Params::new(json!("hello")))?;
+    assert_eq!(params.get_value(), &json!("HELLO"));
+
+    // without stdin the value is only an argument
+    let config = json!({ "script": "cat; echo \"$1\"" });
+    let params = run_script(config, Params::new(json!("hello")))?;
+    assert_eq!(params.get_value(), &json!("hello"));
+    Ok(())
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn timeout_kills_spawned_processes() -> anyhow::Result<()> {
+    let pid_file = std::env::temp_dir().join(format!("geekbar-{}.pid", uuid::Uuid::new_v4()));
+    let config = json!({
+        "script": "sleep 30 & echo $! > \"$1\"; wait",
+        "timeout": 1
+    });
+    let start = Instant::now();
+    let result = run_script(config, Params::new(json!(pid_file)));
+    assert!(matches!(result, Err(Error::Error(message)) if message.contains("timed out")));
+    assert!(start.elapsed() < Duration::from_secs(10));
+
+    let pid = std::fs::read_to_string(&pid_file)?;
+    std::fs::remove_file(&pid_file)?;
+    let deadline = Instant::now() + Duration::from_secs(5);
+    while !exited(pid.trim()) {
+        assert!(
+            Instant::now() < deadline,
+            "background process {} survived",
+            pid
+        );
+        std::thread::sleep(Duration::from_millis(10));
+    }
+    Ok(())
+}
+
+#[test]
+fn cancellation_stops_script() {
+    let cancellation = CancellationToken::default();
+    let mut params = Params::new(json!(null));
+    params.set_cancellation(cancellation.clone());
+    std::thread::spawn(move || {
+        std::thread::sleep(Duration::from_millis(200));
+        cancellation.cancel();
+    });
+
+    let start = Instant::now();
+    let result = run_script(json!({ "script": "sleep 30" }), params);
+    assert!(matches!(result, Err(Error::Cancelled)));
+    assert!(start.elapsed() < Duration::from_secs(10));
+}
@@ -0,0 +1,43 @@
+mod common;
+
+use common::{serve_once, trigger, workflow, A_ID, B_ID, TRIGGER_ID};
+use geekbar_core::executor::Executor;
+use serde_json::json;
+use std::{sync::Arc, time::Duration};
+
+#[test]
+fn runs_async_and_blocking_nodes() -> anyhow::Result<()> {
+    let (url, server) = serve_once("HTTP/1.1 200 OK\r\nContent-Length: 5\r\n\r\nhello");
+    let request = json!({
+        "id": A_ID,
+        "type": "Request",
+        "config": { "url": url, "response": { "type": "Text" } }
+    });
+    let script = json!({
+        "id": B_ID,
+        "type": "RunScript",
+        "config": { "script": "echo \"$1 world\"" }
+    });
+    let workflow = workflow(
+        vec![trigger(TRIGGER_ID, "kw"), request, script],
+        json!([
+            { "from": TRIGGER_ID, "to": A_ID },
+            { "from": A_ID, "to": B_ID }
+        ]),
+    )?;
+
+    let runtime = tokio::runtime::Runtime::new()?;
+    let mut executor = Executor::with_runtime(runtime.handle().clone());
+    executor.add_workflow(Arc::new(workflow))?;
+
+    assert!(executor.trigger("kw", None::<()>)?);
+    let mut values = Vec::new();
+    for _ in 0..3 {
+        let work = executor.receiver().recv_timeout(Duration::from_secs(5))?;
+        values.push(work.params.get_value().clone());
+        executor.receive_work(work, None, &[])?;
+    }
+    assert_eq!(values[1..], [json!("hello"), json!("hello world")]);
+    server.join().unwrap();
+    Ok(())
+}
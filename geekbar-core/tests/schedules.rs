@@ -0,0 +1,50 @@
+mod common;
+
+use common::workflow;
+use geekbar_core::executor::Executor;
+use serde_json::json;
+use std::{collections::HashSet, sync::Arc, time::Duration};
+
+fn schedule_workflow() -> anyhow::Result<Arc<geekbar_core::workflow::Workflow>> {
+    let trigger = json!({
+        "id": uuid::Uuid::new_v4(),
+        "type": "Trigger",
+        "config": { "type": "Schedule", "interval": 1, "title": "tick" }
+    });
+    Ok(Arc::new(workflow(vec![trigger], json!([]))?))
+}
+
+#[test]
+fn same_schedule_in_two_workflows() -> anyhow::Result<()> {
+    let mut executor = Executor::with_workers(1);
+    let first = schedule_workflow()?;
+    let second = schedule_workflow()?;
+    executor.add_workflow(first.clone())?;
+    // schedules don't collide under the default `Reject` policy
+    executor.add_workflow(second.clone())?;
+    assert!(!executor.has_trigger("@every 1s"));
+    assert_eq!(executor.scheduler().next_fire_times().len(), 2);
+
+    let mut fired = HashSet::new();
+    while fired.len() < 2 {
+        let scheduled = executor
+            .schedule_receiver()
+            .recv_timeout(Duration::from_secs(5))?;
+        fired.insert(scheduled.workflow_id);
+        assert!(executor.trigger_node(scheduled.workflow_id, scheduled.node_id, None::<()>)?);
+    }
+    assert_eq!(fired, HashSet::from([first.id, second.id]));
+
+    let work = executor.receiver().recv_timeout(Duration::from_secs(5))?;
+    assert!(fired.contains(&work.workflow_id()));
+
+    // removing one keeps the other's job
+    executor.remove_workflow(first.id)?;
+    let next = executor.scheduler().next_fire_times();
+    assert_eq!(next.len(), 1);
+    assert!(next
+        .keys()
+        .all(|scheduled| scheduled.workflow_id == second.id));
+    assert!(!executor.trigger_node(first.id, uuid::Uuid::new_v4(), None::<()>)?);
+    Ok(())
+}
@@ -0,0 +1,79 @@
+use geekbar_core::{
+    cancel::CancellationToken,
+    node::Node,
+    nodes::{
+        list_filter::{ListOption, ListOptionMark},
+        script_filter::ScriptFilter,
+    },
+    params::Params,
+    prompt::Prompt,
+    Error,
+};
+use serde_json::json;
+use std::time::{Duration, Instant};
+
+fn filter(config: serde_json::Value, params: Params) -> geekbar_core::Result<Params> {
+    let node: ScriptFilter = serde_json::from_value(config).unwrap();
+    node.execute(params)
+}
+
+fn options(mut params: Params) -> Vec<ListOption> {
+    match params.take_prompt() {
+        Some(Prompt::Select { options, .. } | Prompt::FuzzySelect { options, .. }) => options,
+        prompt => panic!("unexpected prompt {:?}", prompt),
+    }
+}
+
+#[test]
+fn stdout_becomes_options() -> anyhow::Result<()> {
+    let config = json!({
+        "script": "printf '[{\"title\":\"%s\",\"value\":1,\"description\":\"first\"},{\"title\":\"b\",\"value\":2}]' \"$1\""
+    });
+    let options = options(filter(config, Params::new(json!("a")))?)
+        .into_iter()
+        .map(|option| (option.title, option.value, option.description))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        options,
+        vec![
+            ("a".to_string(), json!(1), Some("first".to_string())),
+            ("b".to_string(), json!(2), None),
+        ]
+    );
+
+    let config = json!({ "script": "echo '[]'", "fuzzy": true });
+    let mut params = filter(config, Params::new(json!(null)))?;
+    assert!(matches!(
+        params.take_prompt(),
+        Some(Prompt::FuzzySelect { .. })
+    ));
+    Ok(())
+}
+
+#[test]
+fn malformed_output_is_an_error_option() -> anyhow::Result<()> {
+    let params = filter(
+        json!({ "script": "echo not json" }),
+        Params::new(json!(null)),
+    )?;
+    let options = options(params);
+    assert_eq!(options.len(), 1);
+    assert!(matches!(options[0].mark, Some(ListOptionMark::Error)));
+    Ok(())
+}
+
+#[test]
+fn cancellation_is_not_an_option() {
+    let cancellation = CancellationToken::default();
+    let mut params = Params::new(json!(null));
+    params.set_cancellation(cancellation.clone());
+    std::thread::spawn(move || {
+        std::thread::sleep(Duration::from_millis(200));
+        cancellation.cancel();
+    });
+
+    let start = Instant::now();
+    let result = filter(json!({ "script": "sleep 30" }), params);
+    assert!(matches!(result, Err(Error::Cancelled)));
+    assert!(start.elapsed() < Duration::from_secs(10));
+}
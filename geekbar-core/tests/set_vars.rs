@@ -0,0 +1,85 @@
+mod common;
+
+use common::{trigger, workflow, A_ID, B_ID, TRIGGER_ID};
+use geekbar_core::{executor::Executor, node::Node, nodes::set_vars::SetVars, params::Params};
+use serde_json::json;
+use std::{sync::Arc, time::Duration};
+
+const C_ID: &str = "d1f0a4c6-5b0e-4f0e-9a57-0c1e2f3a4b5c";
+
+fn set_vars(config: serde_json::Value, params: Params) -> geekbar_core::Result<Params> {
+    let node: SetVars = serde_json::from_value(config).unwrap();
+    node.execute(params)
+}
+
+#[test]
+fn renders_before_changing_vars() -> anyhow::Result<()> {
+    let mut params = Params::new(json!("value"));
+    params.set_var("old", json!("x"));
+    params.set_var("gone", json!("y"));
+    params.set_var("keep", json!("k"));
+
+    let params = set_vars(
+        json!({
+            "vars": { "a": "{{old}}-{{gone}}", "old": "new", "captured": "set" },
+            "remove": ["gone"],
+            "capture": "captured"
+        }),
+        params,
+    )?;
+    // rendered against the incoming vars
+    assert_eq!(params.get_var("a"), Some(&json!("x-y")));
+    assert_eq!(params.get_var("old"), Some(&json!("new")));
+    assert_eq!(params.get_var("gone"), None);
+    assert_eq!(params.get_var("keep"), Some(&json!("k")));
+    // set comes after capture
+    assert_eq!(params.get_var("captured"), Some(&json!("set")));
+
+    let params = set_vars(
+        json!({ "vars": { "b": "{{keep}}" }, "clear": true, "capture": "c" }),
+        params,
+    )?;
+    let mut vars = params.get_vars().keys().cloned().collect::<Vec<_>>();
+    vars.sort();
+    assert_eq!(vars, vec!["b", "c"]);
+    assert_eq!(params.get_var("b"), Some(&json!("k")));
+    assert_eq!(params.get_var("c"), Some(&json!("value")));
+    Ok(())
+}
+
+#[test]
+fn removed_var_is_absent_downstream() -> anyhow::Result<()> {
+    let nodes = vec![
+        trigger(TRIGGER_ID, "kw"),
+        json!({ "id": A_ID, "type": "SetVars", "config": { "vars": { "t": "1" } } }),
+        json!({
+            "id": B_ID,
+            "type": "SetVars",
+            "config": { "vars": { "seen": "[{{t}}]" }, "remove": ["t"] }
+        }),
+        json!({ "id": C_ID, "type": "SetVars", "config": { "vars": { "after": "[{{t}}]" } } }),
+    ];
+    let workflow = workflow(
+        nodes,
+        json!([
+            { "from": TRIGGER_ID, "to": A_ID },
+            { "from": A_ID, "to": B_ID },
+            { "from": B_ID, "to": C_ID }
+        ]),
+    )?;
+    let mut executor = Executor::with_workers(1);
+    executor.add_workflow(Arc::new(workflow))?;
+
+    assert!(executor.trigger("kw", None::<()>)?);
+    let last = loop {
+        let work = executor.receiver().recv_timeout(Duration::from_secs(5))?;
+        if work.node_id().to_string() == C_ID {
+            break work;
+        }
+        executor.receive_work(work, None, &[])?;
+    };
+    assert_eq!(last.params.get_var("t"), None);
+    assert_eq!(last.params.get_var("seen"), Some(&json!("[1]")));
+    assert_eq!(last.params.get_var("after"), Some(&json!("[]")));
+    Ok(())
+}
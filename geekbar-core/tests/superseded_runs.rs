@@ -0,0 +1,124 @@
+mod common;
+
+use common::{trigger, workflow, A_ID, B_ID, TRIGGER_ID};
+use geekbar_core::executor::Executor;
+use serde_json::{json, Value};
+use std::{
+    io::Read,
+    net::TcpListener,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+fn keyword_executor(next: Value) -> anyhow::Result<Executor> {
+    let workflow = workflow(
+        vec![trigger(TRIGGER_ID, "kw"), next],
+        json!([{ "from": TRIGGER_ID, "to": A_ID }]),
+    )?;
+    let mut executor = Executor::with_workers(2);
+    executor.add_workflow(Arc::new(workflow))?;
+    Ok(executor)
+}
+
+#[test]
+fn new_input_cancels_request_in_flight() -> anyhow::Result<()> {
+    // accepts the request and never answers
+    let listener = TcpListener::bind("127.0.0.1:0")?;
+    let request = json!({
+        "id": A_ID,
+        "type": "Request",
+        "config": { "url": format!("http://{}", listener.local_addr()?) }
+    });
+    let executor = keyword_executor(request)?;
+
+    assert!(executor.trigger("kw", Some("a"))?);
+    let work = executor.receiver().recv_timeout(Duration::from_secs(5))?;
+    let run_id = work.run_id();
+    executor.receive_work(work, None, &[])?;
+    let (mut stream, _) = listener.accept()?;
+
+    assert!(executor.trigger("kw", Some("ab"))?);
+    assert!(!executor.cancel(run_id));
+
+    // the superseded request drops its connection instead of waiting for the timeout
+    let start = Instant::now();
+    stream.set_read_timeout(Some(Duration::from_secs(10)))?;
+    let mut buf = [0; 4096];
+    while stream.read(&mut buf)? > 0 {}
+    assert!(start.elapsed() < Duration::from_secs(10));
+    Ok(())
+}
+
+#[test]
+fn new_input_keeps_acting_run() -> anyhow::Result<()> {
+    let action = json!({
+        "id": A_ID,
+        "type": "RunScript",
+        "config": { "script": "sleep 0.5; echo done" }
+    });
+    let executor = keyword_executor(action)?;
+
+    assert!(executor.trigger("kw", Some("a"))?);
+    let work = executor.receiver().recv_timeout(Duration::from_secs(5))?;
+    let run_id = work.run_id();
+    // picking a value moves the run past its prompt
+    executor.receive_work(work, Some(json!("picked")), &[])?;
+
+    assert!(executor.trigger("kw", Some("ab"))?);
+    let mut works = Vec::new();
+    for _ in 0..2 {
+        works.push(executor.receiver().recv_timeout(Duration::from_secs(5))?);
+    }
+    let acted = works
+        .iter()
+        .find(|work| work.run_id() == run_id)
+        .expect("acting run was cancelled");
+    assert_eq!(acted.node_id().to_string(), A_ID);
+    assert_eq!(acted.params.get_value(), &json!("done"));
+    Ok(())
+}
+
+#[test]
+fn new_input_keeps_run_answered_without_value() -> anyhow::Result<()> {
+    let filter = json!({
+        "id": A_ID,
+        "type": "ListFilter",
+        "config": { "options": [{ "title": "a", "value": null }] }
+    });
+    let action = json!({
+        "id": B_ID,
+        "type": "RunScript",
+        "config": { "script": "sleep 0.5; echo done" }
+    });
+    let workflow = workflow(
+        vec![trigger(TRIGGER_ID, "kw"), filter, action],
+        json!([
+            { "from": TRIGGER_ID, "to": A_ID },
+            { "from": A_ID, "to": B_ID }
+        ]),
+    )?;
+    let mut executor = Executor::with_workers(2);
+    executor.add_workflow(Arc::new(workflow))?;
+
+    assert!(executor.trigger("kw", Some("a"))?);
+    let work = executor.receiver().recv_timeout(Duration::from_secs(5))?;
+    let run_id = work.run_id();
+    executor.receive_work(work, None, &[])?;
+    let prompt = executor.receiver().recv_timeout(Duration::from_secs(5))?;
+    assert_eq!(prompt.node_id().to_string(), A_ID);
+    assert!(prompt.params.prompt.is_some());
+    // answering the prompt without a value still moves the run past it
+    executor.receive_work(prompt, None, &[])?;
+
+    assert!(executor.trigger("kw", Some("ab"))?);
+    let mut works = Vec::new();
+    for _ in 0..2 {
+        works.push(executor.receiver().recv_timeout(Duration::from_secs(5))?);
+    }
+    let acted = works
+        .iter()
+        .find(|work| work.run_id() == run_id)
+        .expect("answered run was cancelled");
+    assert_eq!(acted.node_id().to_string(), B_ID);
+    Ok(())
+}
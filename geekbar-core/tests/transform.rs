@@ -0,0 +1,101 @@
+use geekbar_core::{
+    node::Node,
+    nodes::transform::Transform,
+    params::Params,
+    validator::{self, DiagnosticKind},
+    workflow::WorkflowConfig,
+};
+use serde_json::json;
+
+fn transform(config: serde_json::Value) -> Transform {
+    serde_json::from_value(config).unwrap()
+}
+
+#[test]
+fn reshapes_response_into_options() -> anyhow::Result<()> {
+    let node = transform(json!({
+        "steps": [
+            { "type": "Pick", "path": "data.items" },
+            { "type": "Map", "steps": [
+                { "type": "Object", "fields": {
+                    "title": [{ "type": "Pick", "path": "/name" }],
+                    "value": [{ "type": "Pick", "path": "id" }]
+                }}
+            ]}
+        ],
+        "vars": {
+            "total": [{ "type": "Pick", "path": "data.total" }]
+        }
+    }));
+    let response = json!({
+        "data": { "total": 2, "items": [{ "id": 1, "name": "a" }, { "id": 2, "name": "b" }] }
+    });
+
+    let params = node.execute(Params::new(response))?;
+    assert_eq!(
+        params.get_value(),
+        &json!([{ "title": "a", "value": 1 }, { "title": "b", "value": 2 }])
+    );
+    assert_eq!(params.get_var("total"), Some(&json!(2)));
+    Ok(())
+}
+
+#[test]
+fn splits_joins_and_captures() -> anyhow::Result<()> {
+    let node = transform(json!({
+        "steps": [
+            { "type": "Split", "separator": "," },
+            { "type": "Map", "steps": [{ "type": "Regex", "pattern": "\\d+" }] },
+            { "type": "Join", "separator": "+" }
+        ],
+        "vars": {
+            "named": [{ "type": "Regex", "pattern": "(?P<key>\\w+)=(?P<val>\\w+)" }],
+            "groups": [{ "type": "Regex", "pattern": "(\\w+)=(\\w+)", "all": true }],
+            "none": [{ "type": "Regex", "pattern": "^x" }]
+        }
+    }));
+
+    let params = node.execute(Params::new(json!("a=1,b=22")))?;
+    assert_eq!(params.get_value(), &json!("1+22"));
+    assert_eq!(
+        params.get_var("named"),
+        Some(&json!({ "key": "a", "val": "1" }))
+    );
+    assert_eq!(
+        params.get_var("groups"),
+        Some(&json!([["a", "1"], ["b", "22"]]))
+    );
+    assert_eq!(params.get_var("none"), Some(&json!(null)));
+
+    let err = transform(json!({ "steps": [{ "type": "Join", "separator": "," }] }))
+        .execute(Params::new(json!("text")))
+        .map(|_| ())
+        .unwrap_err();
+    assert!(err.to_string().contains("expects an array"));
+    Ok(())
+}
+
+#[test]
+fn reports_invalid_regex() -> anyhow::Result<()> {
+    let config: WorkflowConfig = serde_json::from_value(json!({
+        "id": "00000000-0000-0000-0000-000000000001",
+        "title": "transform",
+        "nodes": [
+            {
+                "id": "00000000-0000-0000-0000-000000000002",
+                "type": "Transform",
+                "config": { "steps": [{ "type": "Map", "steps": [{ "type": "Regex", "pattern": "(" }] }] }
+            }
+        ],
+        "links": []
+    }))?;
+
+    let diagnostics = validator::validate_config(&config);
+    assert!(
+        diagnostics
+            .iter()
+            .any(|diagnostic| diagnostic.is_error()
+                && diagnostic.kind == DiagnosticKind::InvalidRegex)
+    );
+    Ok(())
+}
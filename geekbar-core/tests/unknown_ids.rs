@@ -0,0 +1,73 @@
+mod common;
+
+use common::{trigger, workflow, A_ID as MISSING_ID, TRIGGER_ID};
+use geekbar_core::{executor::Executor, params::Params, workflow::Workflow, Error};
+use serde_json::json;
+use std::{sync::Arc, time::Duration};
+use uuid::Uuid;
+
+fn dangling_workflow() -> anyhow::Result<Workflow> {
+    workflow(
+        vec![trigger(TRIGGER_ID, "test")],
+        json!([{ "from": TRIGGER_ID, "to": MISSING_ID }]),
+    )
+}
+
+#[test]
+fn next_nodes_reports_dangling_link() -> anyhow::Result<()> {
+    let workflow = dangling_workflow()?;
+    let result = workflow.next_nodes(&TRIGGER_ID.parse()?, &Params::default(), &[]);
+    assert!(matches!(
+        result,
+        Err(Error::DanglingLink { from, to })
+            if from.to_string() == TRIGGER_ID && to.to_string() == MISSING_ID
+    ));
+    Ok(())
+}
+
+#[test]
+fn remove_unknown_workflow() {
+    let mut executor = Executor::with_workers(1);
+    let workflow_id = Uuid::new_v4();
+    assert!(matches!(
+        executor.remove_workflow(workflow_id),
+        Err(Error::WorkflowNotFound(id)) if id == workflow_id
+    ));
+}
+
+#[test]
+fn receive_work_reports_dangling_link() -> anyhow::Result<()> {
+    let mut executor = Executor::with_workers(1);
+    let workflow = Arc::new(dangling_workflow()?);
+    let workflow_id = workflow.id;
+    executor.add_workflow(workflow)?;
+
+    assert!(executor.trigger("test", None::<()>)?);
+    let work = executor.receiver().recv_timeout(Duration::from_secs(5))?;
+    assert!(matches!(
+        executor.receive_work(work, None, &[]),
+        Err(Error::DanglingLink { .. })
+    ));
+
+    executor.remove_workflow(workflow_id)?;
+    assert!(!executor.has_workflow(workflow_id));
+    Ok(())
+}
+
+#[test]
+fn stale_work_after_remove_is_ignored() -> anyhow::Result<()> {
+    let mut executor = Executor::with_workers(1);
+    let workflow = Arc::new(dangling_workflow()?);
+    let workflow_id = workflow.id;
+    executor.add_workflow(workflow)?;
+
+    assert!(executor.trigger("test", None::<()>)?);
+    let work = executor.receiver().recv_timeout(Duration::from_secs(5))?;
+    executor.cancel(work.run_id());
+    executor.remove_workflow(workflow_id)?;
+
+    // the run is gone, so the work is dropped instead of panicking
+    executor.receive_work(work, None, &[])?;
+    assert!(!executor.has_trigger("test"));
+    Ok(())
+}
@@ -0,0 +1,84 @@
+mod common;
+
+use common::{node, trigger, workflow, A_ID, B_ID, TRIGGER_ID};
+use geekbar_core::{
+    executor::CollisionPolicy,
+    validator::{DiagnosticKind, Severity},
+    workflow::Workflow,
+};
+use serde_json::json;
+use std::sync::Arc;
+use uuid::Uuid;
+
+fn kinds(workflow: &Workflow) -> Vec<DiagnosticKind> {
+    workflow.diagnostics().iter().map(|d| d.kind).collect()
+}
+
+#[test]
+fn valid_workflow_has_no_diagnostics() -> anyhow::Result<()> {
+    let workflow = workflow(
+        vec![trigger(TRIGGER_ID, "test"), node(A_ID)],
+        json!([{ "from": TRIGGER_ID, "to": A_ID }]),
+    )?;
+    assert!(workflow.diagnostics().is_empty());
+    Ok(())
+}
+
+#[test]
+fn reports_config_problems() -> anyhow::Result<()> {
+    let workflow = workflow(
+        vec![node(A_ID), node(A_ID), node(B_ID)],
+        json!([
+            { "from": A_ID, "to": B_ID },
+            { "from": B_ID, "to": A_ID },
+            { "from": B_ID, "to": TRIGGER_ID },
+            {
+                "from": A_ID,
+                "to": B_ID,
+                "condition": { "type": "Value", "operator": { "Match": "(" } }
+            }
+        ]),
+    )?;
+    let kinds = kinds(&workflow);
+    for kind in [
+        DiagnosticKind::DuplicateNode,
+        DiagnosticKind::NoTrigger,
+        DiagnosticKind::DanglingLink,
+        DiagnosticKind::Cycle,
+        DiagnosticKind::UnreachableNode,
+        DiagnosticKind::InvalidRegex,
+    ] {
+        assert!(kinds.contains(&kind), "missing {:?} in {:?}", kind, kinds);
+    }
+    Ok(())
+}
+
+#[test]
+fn allowed_cycle_is_not_reported() -> anyhow::Result<()> {
+    let workflow = workflow(
+        vec![trigger(TRIGGER_ID, "test"), node(A_ID), node(B_ID)],
+        json!([
+            { "from": TRIGGER_ID, "to": A_ID },
+            { "from": A_ID, "to": B_ID },
+            { "from": B_ID, "to": A_ID, "allow_cycle": true }
+        ]),
+    )?;
+    assert!(workflow.diagnostics().is_empty());
+    Ok(())
+}
+
+#[test]
+fn reports_keyword_taken_by_other_workflow() -> anyhow::Result<()> {
+    let loaded = Arc::new(workflow(vec![trigger(TRIGGER_ID, "test")], json!([]))?);
+    let workflow = workflow(vec![trigger(A_ID, "test")], json!([]))?;
+
+    let node_id: Uuid = A_ID.parse()?;
+    let diagnostics =
+        geekbar_core::validator::validate(&workflow, &[loaded], CollisionPolicy::Reject);
+    assert!(diagnostics
+        .iter()
+        .any(|d| d.kind == DiagnosticKind::DuplicateTrigger
+            && d.severity == Severity::Error
+            && d.nodes == vec![node_id]));
+    Ok(())
+}
@@ -1,48 +1,93 @@
-use geekbar::Work;
+use geekbar::{Diagnostic, Geekbar, Modifier, TriggerCandidate, Work};
 use serde_json::Value;
-use tauri::{command, State};
+use tauri::{async_runtime, command, State};
 use uuid::Uuid;
 
 use crate::GeekbarState;
 
+/// Locks geekbar and runs `f` on a blocking thread,
+/// so neither waiting for the lock nor the work stalls the async runtime.
+async fn run_blocking<T, F>(geekbar: &GeekbarState, f: F) -> Result<T, String>
+where
+    F: FnOnce(&mut Geekbar) -> anyhow::Result<T> + Send + 'static,
+    T: Send + 'static,
+{
+    let geekbar = geekbar.0.clone();
+    async_runtime::spawn_blocking(move || f(&mut geekbar.lock().unwrap()))
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())
+}
+
 #[command]
 pub async fn execute(
     geekbar: State<'_, GeekbarState>,
     work: Work,
     value: Option<Value>,
+    modifiers: Option<Vec<Modifier>>,
 ) -> Result<(), String> {
-    geekbar
-        .0
-        .lock()
-        .unwrap()
-        .executor
-        .receive_work(work, value)
-        .map_err(|e| e.to_string())
+    run_blocking(&geekbar, move |geekbar| {
+        geekbar.receive_work(work, value, &modifiers.unwrap_or_default())
+    })
+    .await
 }
 
 #[command]
 pub async fn trigger(
     geekbar: State<'_, GeekbarState>,
-    trigger_id: &str,
+    trigger_id: String,
     value: Option<Value>,
 ) -> Result<bool, String> {
-    geekbar
-        .0
-        .lock()
-        .unwrap()
-        .executor
-        .trigger(trigger_id, value)
-        .map_err(|e| e.to_string())
+    run_blocking(&geekbar, move |geekbar| {
+        Ok(geekbar.executor.trigger(&trigger_id, value)?)
+    })
+    .await
+}
+
+#[command]
+pub async fn cancel(geekbar: State<'_, GeekbarState>, run_id: Uuid) -> Result<bool, String> {
+    run_blocking(&geekbar, move |geekbar| Ok(geekbar.executor.cancel(run_id))).await
+}
+
+#[command]
+pub async fn match_keywords(
+    geekbar: State<'_, GeekbarState>,
+    input: String,
+) -> Result<Vec<TriggerCandidate>, String> {
+    run_blocking(&geekbar, move |geekbar| {
+        Ok(geekbar.executor.match_keywords(&input))
+    })
+    .await
+}
+
+#[command]
+pub async fn pick_keyword(
+    geekbar: State<'_, GeekbarState>,
+    trigger_id: String,
+    query: String,
+) -> Result<(), String> {
+    run_blocking(&geekbar, move |geekbar| {
+        geekbar.pick_keyword(&trigger_id, &query);
+        Ok(())
+    })
+    .await
+}
+
+#[command]
+pub async fn reset_frecency(
+    geekbar: State<'_, GeekbarState>,
+    workflow_id: Uuid,
+) -> Result<(), String> {
+    run_blocking(&geekbar, move |geekbar| {
+        geekbar.reset_frecency(workflow_id);
+        Ok(())
+    })
+    .await
 }
 
 #[command]
 pub async fn fetch_all_workflows(geekbar: State<'_, GeekbarState>) -> Result<Vec<String>, String> {
-    geekbar
-        .0
-        .lock()
-        .unwrap()
-        .fetch_all_workflows_json()
-        .map_err(|e| e.to_string())
+    run_blocking(&geekbar, |geekbar| geekbar.fetch_all_workflows_json()).await
 }
 
 #[command]
@@ -50,26 +95,22 @@ pub async fn create_workflow(
     geekbar: State<'_, GeekbarState>,
     workflow_name: String,
 ) -> Result<String, String> {
-    let mut geekbar = geekbar.0.lock().unwrap();
-    let workflow_id = geekbar
-        .create_workflow(&workflow_name)
-        .map_err(|e| e.to_string())?;
-    let geekbar_json = geekbar.get_workflow_json(workflow_id);
-
-    Ok(geekbar_json.to_string())
+    run_blocking(&geekbar, move |geekbar| {
+        let workflow_id = geekbar.create_workflow(&workflow_name)?;
+        Ok(geekbar.get_workflow_json(workflow_id)?.to_string())
+    })
+    .await
 }
 
 #[command]
 pub async fn save_workflow(
     geekbar: State<'_, GeekbarState>,
     workflow_json: String,
-) -> Result<(), String> {
-    geekbar
-        .0
-        .lock()
-        .unwrap()
-        .save_workflow_json(&workflow_json)
-        .map_err(|e| e.to_string())
+) -> Result<Vec<Diagnostic>, String> {
+    run_blocking(&geekbar, move |geekbar| {
+        geekbar.save_workflow_json(&workflow_json)
+    })
+    .await
 }
 
 #[command]
@@ -77,12 +118,10 @@ pub async fn delete_workflow(
     geekbar: State<'_, GeekbarState>,
     workflow_id: Uuid,
 ) -> Result<(), String> {
-    geekbar
-        .0
-        .lock()
-        .unwrap()
-        .delete_workflow(workflow_id)
-        .map_err(|e| e.to_string())
+    run_blocking(&geekbar, move |geekbar| {
+        geekbar.delete_workflow(workflow_id)
+    })
+    .await
 }
 
 #[command]
@@ -91,10 +130,5 @@ pub async fn move_workflow(
     from: usize,
     to: usize,
 ) -> Result<(), String> {
-    geekbar
-        .0
-        .lock()
-        .unwrap()
-        .move_workflow(from, to)
-        .map_err(|e| e.to_string())
+    run_blocking(&geekbar, move |geekbar| geekbar.move_workflow(from, to)).await
 }
@@ -0,0 +1,33 @@
+use geekbar::HotkeyEvent;
+use tauri::{AppHandle, GlobalShortcutManager, Manager, Wry};
+
+use crate::GeekbarState;
+
+pub fn handle_event(app: &AppHandle<Wry>, event: HotkeyEvent) {
+    let mut manager = app.global_shortcut_manager();
+    match event {
+        HotkeyEvent::Register(hotkey) => {
+            let geekbar = app.state::<GeekbarState>().0.clone();
+            let trigger_id = hotkey.id.clone();
+            let result = manager.register(hotkey.accelerator(), move || {
+                tracing::info!(trigger_id, "hotkey pressed");
+                let result = geekbar
+                    .lock()
+                    .unwrap()
+                    .executor
+                    .trigger(&trigger_id, None::<()>);
+                if let Err(err) = result {
+                    tracing::error!(trigger_id, %err, "hotkey trigger failed");
+                }
+            });
+            if let Err(err) = result {
+                tracing::error!(id = %hotkey.id, %err, "register hotkey failed");
+            }
+        }
+        HotkeyEvent::Unregister(hotkey) => {
+            if let Err(err) = manager.unregister(hotkey.accelerator()) {
+                tracing::error!(id = %hotkey.id, %err, "unregister hotkey failed");
+            }
+        }
+    }
+}
@@ -4,15 +4,16 @@
 )]
 
 mod commands;
+mod hotkey;
 mod system_tray;
 mod utils;
 
-use geekbar::Geekbar;
+use geekbar::{FileWatchEvent, Geekbar};
 use std::{
     sync::{Arc, Mutex},
     thread,
 };
-use tauri::Manager;
+use tauri::{async_runtime::RuntimeHandle, Manager};
 use tracing_subscriber::{fmt, layer::SubscriberExt, util::SubscriberInitExt};
 
 pub struct GeekbarState(Arc<Mutex<Geekbar>>);
@@ -20,8 +21,13 @@ pub struct GeekbarState(Arc<Mutex<Geekbar>>);
 fn main() -> anyhow::Result<()> {
     tracing_subscriber::registry().with(fmt::layer()).init();
 
-    let geekbar = Geekbar::init()?;
+    let geekbar = match tauri::async_runtime::handle() {
+        RuntimeHandle::Tokio(handle) => Geekbar::init_with_runtime(handle)?,
+    };
     let receiver = geekbar.receiver();
+    let hotkey_receiver = geekbar.hotkey_receiver();
+    let schedule_receiver = geekbar.schedule_receiver();
+    let file_watch_receiver = geekbar.file_watch_receiver();
     tauri::Builder::default()
         .setup(|app| {
             let app_handle = app.handle();
@@ -31,6 +37,51 @@ fn main() -> anyhow::Result<()> {
                 }
             });
 
+            let app_handle = app.handle();
+            thread::spawn(move || {
+                while let Ok(event) = hotkey_receiver.recv() {
+                    hotkey::handle_event(&app_handle, event);
+                }
+            });
+
+            let geekbar = app.state::<GeekbarState>().0.clone();
+            thread::spawn(move || {
+                while let Ok(scheduled) = schedule_receiver.recv() {
+                    let result = geekbar.lock().unwrap().executor.trigger_node(
+                        scheduled.workflow_id,
+                        scheduled.node_id,
+                        None::<()>,
+                    );
+                    if let Err(err) = result {
+                        tracing::error!(?scheduled, %err, "schedule trigger failed");
+                    }
+                }
+            });
+
+            let geekbar = app.state::<GeekbarState>().0.clone();
+            thread::spawn(move || {
+                while let Ok(event) = file_watch_receiver.recv() {
+                    let FileWatchEvent {
+                        workflow_id,
+                        node_id,
+                        paths,
+                    } = event;
+                    let result = geekbar.lock().unwrap().executor.trigger_node(
+                        workflow_id,
+                        node_id,
+                        Some(paths),
+                    );
+                    if let Err(err) = result {
+                        tracing::error!(%workflow_id, %node_id, %err, "file watch trigger failed");
+                    }
+                }
+            });
+
+            let geekbar = app.state::<GeekbarState>().0.clone();
+            if let Err(err) = geekbar::api::serve(geekbar) {
+                tracing::error!(%err, "start api failed");
+            }
+
             let app_handle = app.handle();
             app.listen_global("hide_splashscreen", move |_event| {
                 utils::hide_splashscreen(&app_handle);
@@ -49,6 +100,10 @@ fn main() -> anyhow::Result<()> {
         .invoke_handler(tauri::generate_handler![
             commands::trigger,
             commands::execute,
+            commands::cancel,
+            commands::match_keywords,
+            commands::pick_keyword,
+            commands::reset_frecency,
             commands::fetch_all_workflows,
             commands::create_workflow,
             commands::delete_workflow,
@@ -57,8 +112,18 @@ fn main() -> anyhow::Result<()> {
         ])
         .system_tray(system_tray::init())
         .on_system_tray_event(system_tray::event_handler)
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .build(tauri::generate_context!())
+        .expect("error while building tauri application")
+        .run(|app, event| {
+            // picks are saved in the background, write the pending ones before exiting
+            if let tauri::RunEvent::Exit = event {
+                app.state::<GeekbarState>()
+                    .0
+                    .lock()
+                    .unwrap()
+                    .flush_frecency();
+            }
+        });
 
     Ok(())
 }
@@ -18,7 +18,7 @@ pub fn event_handler(app: &AppHandle<Wry>, event: SystemTrayEvent) {
         SystemTrayEvent::MenuItemClick { id, .. } => match id.as_str() {
             "show" => app.emit_all("show", ()).unwrap(),
             "setting" => show_setting_window(app),
-            "quit" => std::process::exit(0),
+            "quit" => app.exit(0),
             _ => {}
         },
         _ => {}
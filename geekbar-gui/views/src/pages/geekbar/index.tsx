@@ -8,6 +8,20 @@ import {
   AngleRight as DefaultIcon,
 } from "@vicons/fa"
 
+// wraps the matched chars, ranges count code points like the core does
+function highlight(text: string, ranges: Array<[number, number]> = []) {
+  const chars = Array.from(text)
+  const nodes = []
+  let last = 0
+  for (const [start, end] of ranges) {
+    nodes.push(chars.slice(last, start).join(''))
+    nodes.push(<span class="font-bold underline">{chars.slice(start, end).join('')}</span>)
+    last = end
+  }
+  nodes.push(chars.slice(last).join(''))
+  return nodes
+}
+
 export default defineComponent({
   setup() {
     const { state, refs, windowHeight, keydownHandler, clickHandler } = useService()
@@ -78,12 +92,12 @@ export default defineComponent({
                     {/* option title */}
                     <div class="whitespace-nowrap overflow-hidden text-ellipsis" style={{
                       fontSize: `${OPTION_TITLE_FONT_SIZE}px`
-                    }}>{opt.title}</div>
+                    }}>{highlight(opt.title, opt.highlights?.title)}</div>
 
                     {/* option desc */}
                     {vIf(opt.description, () => <div class="whitespace-nowrap overflow-hidden text-ellipsis" style={{
                       fontSize: `${OPTION_DESC_FONT_SIZE}px`,
-                    }}>{opt.description}</div>)}
+                    }}>{highlight(opt.description!, opt.highlights?.description)}</div>)}
                   </div>
                 </div>
               })
@@ -26,9 +26,18 @@ interface Option {
   description?: string;
   value: any;
   mark?: "Hint" | "Error";
+  // matched chars as [start, end) ranges, counted in code points
+  highlights?: {
+    title: Array<[number, number]>;
+    description: Array<[number, number]>;
+  };
   icon?: string;
   work: any;
+  // set on keyword candidates, choosing one completes the input
+  keyword?: string;
 }
+type Modifier = "Ctrl" | "Alt" | "Shift" | "Meta";
+
 export const INPUT_FONT_SIZE = 24;
 export const OPTION_TITLE_FONT_SIZE = 20;
 export const OPTION_DESC_FONT_SIZE = 14;
@@ -154,7 +163,7 @@ export function useService() {
   });
 
   function keydownHandler(e: KeyboardEvent) {
-    const { code, ctrlKey, altKey, shiftKey, metaKey } = e;
+    const { code } = e;
     switch (code) {
       case "ArrowDown": {
         if (state.active === -1) {
@@ -178,11 +187,12 @@ export function useService() {
         }
         const option = state.options[state.active];
         if (option) {
-          executeOption();
+          executeOption(getModifiers(e));
         }
         break;
       }
       case "Escape": {
+        cancel(state.options[0]?.work);
         hideWindow();
         resetState();
         break;
@@ -191,7 +201,16 @@ export function useService() {
   }
 
   function clickHandler(e: MouseEvent, index: number) {
-    executeOption();
+    executeOption(getModifiers(e));
+  }
+
+  function getModifiers(e: KeyboardEvent | MouseEvent) {
+    const modifiers: Array<Modifier> = [];
+    if (e.ctrlKey) modifiers.push("Ctrl");
+    if (e.altKey) modifiers.push("Alt");
+    if (e.shiftKey) modifiers.push("Shift");
+    if (e.metaKey) modifiers.push("Meta");
+    return modifiers;
   }
 
   async function search() {
@@ -202,24 +221,50 @@ export function useService() {
       value: arg,
     });
     if (!triggered) {
-      state.options = []
-      state.active = -1
+      const candidates: Array<any> = await invoke("match_keywords", {
+        input: keyword,
+      });
+      state.options = candidates.map((candidate) => {
+        return {
+          title: candidate.title || candidate.trigger_id,
+          description: `${candidate.trigger_id} · ${candidate.workflow_title}`,
+          value: null,
+          work: null,
+          keyword: candidate.trigger_id,
+        };
+      });
+      state.active = state.options.length ? 0 : -1;
     }
   }
 
-  async function executeOption() {
+  async function cancel(work: any) {
+    const runId = work?.work_node?.run_id;
+    if (runId) {
+      await invoke("cancel", { runId });
+    }
+  }
+
+  async function executeOption(modifiers: Array<Modifier> = []) {
     const option = state.options[state.active];
+    if (option?.keyword) {
+      invoke("pick_keyword", {
+        triggerId: option.keyword,
+        query: state.keyword.trim(),
+      });
+      state.keyword = `${option.keyword} `;
+      return;
+    }
     if (option && !option.mark) {
-      execute(option.work, option.value);
+      execute(option.work, option.value, modifiers);
       hideWindow();
     }
   }
 
-  async function execute(work: any, value = null) {
-    console.log("execute", work, value);
+  async function execute(work: any, value = null, modifiers: Array<Modifier> = []) {
     await invoke("execute", {
       work,
       value,
+      modifiers,
     });
   }
 
@@ -1,4 +1,5 @@
 import { defineComponent, onMounted, reactive } from "vue";
+import { notification } from "../../utils";
 import { vIf } from "../../utils/jsxHelper";
 import { showWindow } from "../../utils/window";
 import Canvas from "./canvas"
@@ -39,7 +40,15 @@ export default defineComponent({
         }
 
         async function saveWorkflowHandler(workflow: ConfigSchema) {
-            await saveWorkflow(workflow)
+            const diagnostics = await saveWorkflow(workflow)
+            diagnostics.forEach((diagnostic) => {
+                notification.create({
+                    type: diagnostic.severity === "Error" ? "error" : "warning",
+                    title: diagnostic.kind,
+                    content: diagnostic.message,
+                    duration: diagnostic.severity === "Error" ? undefined : 5000,
+                })
+            })
         }
 
         return {
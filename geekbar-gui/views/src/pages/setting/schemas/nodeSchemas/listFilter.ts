@@ -1,4 +1,36 @@
-import { NodeConfigSchema, optionSchema } from "../types";
+import { FormItemSchema, NodeConfigSchema, optionSchema } from "../types";
+
+// title and description are templates, e.g. "No results for {{query}}"
+const messageSchema: Array<FormItemSchema> = [
+  {
+    label: "title",
+    prop: "title",
+    required: true,
+    type: "text",
+  },
+  {
+    label: "description",
+    prop: "description",
+    required: false,
+    type: "text",
+  },
+  {
+    label: "mark",
+    prop: "mark",
+    required: false,
+    type: "radio",
+    options: [
+      {
+        label: "hint",
+        value: "Hint",
+      },
+      {
+        label: "error",
+        value: "Error",
+      },
+    ],
+  },
+];
 
 export const listFilterConfigSchema: NodeConfigSchema = {
   type: "ListFilter",
@@ -19,6 +51,40 @@ export const listFilterConfigSchema: NodeConfigSchema = {
           items: optionSchema
         },
       },
+      {
+        label: "items from value",
+        prop: "items",
+        required: false,
+        type: "record",
+        items: {
+          items: [
+            {
+              label: "path",
+              prop: "path",
+              required: false,
+              type: "text",
+            },
+            {
+              label: "title",
+              prop: "title",
+              required: true,
+              type: "text",
+            },
+            {
+              label: "value",
+              prop: "value",
+              required: false,
+              type: "json",
+            },
+            {
+              label: "description",
+              prop: "description",
+              required: false,
+              type: "text",
+            },
+          ],
+        },
+      },
       {
         label: "need args",
         prop: "need_args",
@@ -39,6 +105,33 @@ export const listFilterConfigSchema: NodeConfigSchema = {
           },
         ],
       },
+      {
+        label: "placeholder",
+        prop: "placeholder",
+        required: false,
+        type: "record",
+        items: {
+          items: messageSchema,
+        },
+      },
+      {
+        label: "empty",
+        prop: "empty",
+        required: false,
+        type: "record",
+        items: {
+          items: messageSchema,
+        },
+      },
+      {
+        label: "hint",
+        prop: "hint",
+        required: false,
+        type: "record",
+        items: {
+          items: messageSchema,
+        },
+      },
     ],
   },
 };
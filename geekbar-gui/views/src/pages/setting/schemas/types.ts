@@ -20,6 +20,22 @@ export interface LinkSchema {
   to: string;
   condition?: ConditionSchema;
   modifiers?: Array<"Alt" | "Ctrl" | "Shift" | "Meta">;
+  allow_cycle?: boolean;
+}
+
+export interface Diagnostic {
+  severity: "Error" | "Warning";
+  kind:
+    | "DanglingLink"
+    | "DuplicateNode"
+    | "Cycle"
+    | "UnreachableNode"
+    | "NoTrigger"
+    | "DuplicateTrigger"
+    | "InvalidRegex"
+    | "LoadFailed";
+  nodes: Array<string>;
+  message: string;
 }
 
 export interface NodeConfigSchema {
@@ -37,10 +53,35 @@ export type ConditionSchema =
       conditions: Array<ConditionSchema>;
     }
   | {
-      type: "Eq";
-      values: [string, string];
+      type: "Value";
+      subject?: ConditionSubjectSchema;
+      operator: ValueOperatorSchema;
+    };
+
+export type ConditionSubjectSchema =
+  | {
+      type: "Value";
+      path?: string;
+    }
+  | {
+      type: "Var";
+      name: string;
+      path?: string;
     };
 
+export type ValueOperatorSchema =
+  | { Eq: any }
+  | { Gt: any }
+  | { Ge: any }
+  | { Lt: any }
+  | { Le: any }
+  | { Match: string }
+  | { Between: [any, any] }
+  | { In: any }
+  | { Includes: any }
+  | "NonNull"
+  | "Nullable";
+
 export type FormSchema = {
   items: Array<FormItemSchema>;
 };
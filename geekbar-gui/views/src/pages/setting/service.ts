@@ -1,5 +1,5 @@
 import { invoke } from "@tauri-apps/api";
-import { ConfigSchema } from "./schemas";
+import { ConfigSchema, Diagnostic } from "./schemas";
 
 export async function getAllWorkflows(): Promise<Array<ConfigSchema>> {
   return invoke<string[]>("fetch_all_workflows").then((workflows) => {
@@ -8,7 +8,7 @@ export async function getAllWorkflows(): Promise<Array<ConfigSchema>> {
 }
 
 export async function saveWorkflow(workflow: ConfigSchema) {
-  return invoke("save_workflow", {
+  return invoke<Array<Diagnostic>>("save_workflow", {
     workflowJson: JSON.stringify(workflow)
   })
 }
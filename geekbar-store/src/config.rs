@@ -1,13 +1,49 @@
+use geekbar_core::executor::CollisionPolicy;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Config {
     pub workflows: Vec<Uuid>,
+    #[serde(default)]
+    pub api: ApiConfig,
+    // what to do when workflows share a keyword or shortcut
+    #[serde(default)]
+    pub trigger_collision: CollisionPolicy,
 }
 
 impl Config {
     pub fn default() -> Self {
-        Self { workflows: vec![] }
+        Self {
+            workflows: vec![],
+            api: ApiConfig::default(),
+            trigger_collision: CollisionPolicy::default(),
+        }
+    }
+}
+
+/// Local HTTP endpoint that lets other tools fire triggers.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApiConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default = "_default_api_port")]
+    pub port: u16,
+    // sent by clients as "Authorization: Bearer <token>"
+    #[serde(default)]
+    pub token: String,
+}
+
+const fn _default_api_port() -> u16 {
+    37420
+}
+
+impl Default for ApiConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            port: _default_api_port(),
+            token: String::new(),
+        }
     }
 }
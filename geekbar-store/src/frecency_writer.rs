@@ -0,0 +1,73 @@
+use geekbar_core::frecency::Frecency;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, RecvTimeoutError, Sender};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+/// Writes frecency on a background thread once picks settle, so picking never waits on the disk.
+///
+/// Only the latest snapshot is written, what is still pending is written on `flush` or drop.
+pub struct FrecencyWriter {
+    sender: Option<Sender<Frecency>>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl FrecencyWriter {
+    /// Writes to `path` once no new snapshot came for `delay`.
+    pub fn new(path: PathBuf, delay: Duration) -> Self {
+        let (sender, receiver) = mpsc::channel::<Frecency>();
+        let handle = thread::spawn(move || {
+            let mut pending = None;
+            loop {
+                let received = match pending {
+                    Some(_) => receiver.recv_timeout(delay),
+                    None => receiver.recv().map_err(|_| RecvTimeoutError::Disconnected),
+                };
+                match received {
+                    Ok(frecency) => pending = Some(frecency),
+                    Err(err) => {
+                        if let Some(frecency) = pending.take() {
+                            if let Err(err) = write_frecency(&path, &frecency) {
+                                tracing::error!(%err, "save frecency failed");
+                            }
+                        }
+                        if err == RecvTimeoutError::Disconnected {
+                            return;
+                        }
+                    }
+                }
+            }
+        });
+        Self {
+            sender: Some(sender),
+            handle: Some(handle),
+        }
+    }
+
+    pub fn save(&self, frecency: Frecency) {
+        if let Some(sender) = &self.sender {
+            sender.send(frecency).ok();
+        }
+    }
+
+    /// Writes the pending snapshot and stops the writer, later saves are dropped.
+    pub fn flush(&mut self) {
+        self.sender.take();
+        if let Some(handle) = self.handle.take() {
+            handle.join().ok();
+        }
+    }
+}
+
+impl Drop for FrecencyWriter {
+    fn drop(&mut self) {
+        self.flush();
+    }
+}
+
+pub(crate) fn write_frecency(path: &Path, frecency: &Frecency) -> anyhow::Result<()> {
+    let frecency_json = serde_json::to_string(frecency)?;
+    std::fs::write(path, frecency_json)?;
+
+    Ok(())
+}
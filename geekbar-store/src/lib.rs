@@ -1,5 +1,8 @@
+mod frecency_writer;
 mod store;
 mod workflow_meta;
 mod config;
 
+pub use config::ApiConfig;
+pub use frecency_writer::FrecencyWriter;
 pub use store::Store;
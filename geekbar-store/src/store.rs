@@ -1,14 +1,19 @@
 use anyhow::anyhow;
 use directories::ProjectDirs;
+use geekbar_core::executor::CollisionPolicy;
+use geekbar_core::frecency::Frecency;
 use geekbar_core::workflow::{Workflow, WorkflowConfig};
+use geekbar_core::Error;
 use std::collections::HashMap;
 use std::ops::Index;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use std::time::Duration;
 use std::{fs, vec};
 use uuid::Uuid;
 
-use crate::config::Config;
+use crate::config::{ApiConfig, Config};
+use crate::frecency_writer::{self, FrecencyWriter};
 use crate::workflow_meta::WorkflowMeta;
 
 pub struct Store {
@@ -16,14 +21,18 @@ pub struct Store {
     workflows_meta: HashMap<Uuid, WorkflowMeta>,
     config_path: PathBuf,
     config: Config,
+    frecency_path: PathBuf,
 }
 
 impl Store {
     pub fn init() -> anyhow::Result<Self> {
         let proj_dirs = ProjectDirs::from("top.geekbar", "", "geekbar")
             .ok_or_else(|| anyhow!("Project dir not found"))?;
-        let config_dir = proj_dirs.config_dir();
+        Self::init_in(proj_dirs.config_dir())
+    }
 
+    /// Loads the store from `config_dir`, creating it if needed.
+    pub fn init_in(config_dir: &Path) -> anyhow::Result<Self> {
         let workflows_dir = config_dir.join("workflows");
         if !workflows_dir.exists() {
             std::fs::create_dir_all(&workflows_dir)?;
@@ -38,11 +47,10 @@ impl Store {
         }
 
         let config_path = config_dir.join("config.json");
-        let config = if !config_path.exists() {
+        let config_exists = config_path.exists();
+        let config = if !config_exists {
             let mut config = Config::default();
             config.workflows = workflow_ids;
-            let config_json = serde_json::to_string(&config)?;
-            std::fs::write(&config_path, config_json)?;
             config
         } else {
             let config_json = std::fs::read_to_string(&config_path)?;
@@ -50,21 +58,66 @@ impl Store {
             config
         };
 
-        let store = Self {
+        let mut store = Self {
             workflows_dir,
             workflows_meta,
             config_path,
             config,
+            frecency_path: config_dir.join("frecency.json"),
         };
 
+        // generate api token on first run
+        let token_missing = store.config.api.token.is_empty();
+        if token_missing {
+            store.config.api.token = Uuid::new_v4().simple().to_string();
+        }
+        if !config_exists || token_missing {
+            store.save_config()?;
+        }
+
         Ok(store)
     }
 
-    pub fn get_workflow_json(&self, workflow_id: Uuid) -> &str {
-        self.workflows_meta
+    pub fn api_config(&self) -> &ApiConfig {
+        &self.config.api
+    }
+
+    pub fn collision_policy(&self) -> CollisionPolicy {
+        self.config.trigger_collision
+    }
+
+    /// Loads what the user picked before, starting over if it can't be read.
+    pub fn load_frecency(&self) -> Frecency {
+        if !self.frecency_path.exists() {
+            return Frecency::default();
+        }
+        let frecency = std::fs::read_to_string(&self.frecency_path)
+            .map_err(anyhow::Error::from)
+            .and_then(|json| Ok(serde_json::from_str(&json)?));
+        match frecency {
+            Ok(frecency) => frecency,
+            Err(err) => {
+                tracing::warn!(%err, "load frecency failed");
+                Frecency::default()
+            }
+        }
+    }
+
+    pub fn save_frecency(&self, frecency: &Frecency) -> anyhow::Result<()> {
+        frecency_writer::write_frecency(&self.frecency_path, frecency)
+    }
+
+    /// Saves frecency in the background once no pick came for `delay`.
+    pub fn frecency_writer(&self, delay: Duration) -> FrecencyWriter {
+        FrecencyWriter::new(self.frecency_path.clone(), delay)
+    }
+
+    pub fn get_workflow_json(&self, workflow_id: Uuid) -> anyhow::Result<&str> {
+        let workflow_meta = self
+            .workflows_meta
             .get(&workflow_id)
-            .unwrap()
-            .get_workflow_json()
+            .ok_or(Error::WorkflowNotFound(workflow_id))?;
+        Ok(workflow_meta.get_workflow_json())
     }
 
     pub fn create_workflow(&mut self, name: &str) -> anyhow::Result<Uuid> {
@@ -95,17 +148,14 @@ impl Store {
     }
 
     pub fn delete_workflow(&mut self, workflow_id: Uuid) -> anyhow::Result<()> {
+        if self.workflows_meta.remove(&workflow_id).is_none() {
+            return Err(Error::WorkflowNotFound(workflow_id).into());
+        }
         let workflow_dir_path = self.workflow_dir_path(workflow_id);
-        self.workflows_meta.remove(&workflow_id);
         fs::remove_dir_all(&workflow_dir_path)?;
 
-        let index = self
-            .config
-            .workflows
-            .iter()
-            .position(|e| e.eq(&workflow_id))
-            .unwrap();
-        self.config.workflows.remove(index);
+        self.config.workflows.retain(|e| !e.eq(&workflow_id));
+        self.save_config()?;
 
         Ok(())
     }
@@ -123,13 +173,13 @@ impl Store {
                 .workflows
                 .iter()
                 .position(|w| w.eq(&a.id))
-                .unwrap();
+                .unwrap_or(usize::MAX);
             let b = self
                 .config
                 .workflows
                 .iter()
                 .position(|w| w.eq(&b.id))
-                .unwrap();
+                .unwrap_or(usize::MAX);
 
             a.cmp(&b)
         });
@@ -150,13 +200,13 @@ impl Store {
                 .workflows
                 .iter()
                 .position(|w| w.eq(&a.0))
-                .unwrap();
+                .unwrap_or(usize::MAX);
             let b = self
                 .config
                 .workflows
                 .iter()
                 .position(|w| w.eq(&b.0))
-                .unwrap();
+                .unwrap_or(usize::MAX);
 
             a.cmp(&b)
         });
@@ -165,7 +215,10 @@ impl Store {
     }
 
     pub fn spawn_workflow(&mut self, workflow_id: Uuid) -> anyhow::Result<Arc<Workflow>> {
-        let workflow_meta = self.workflows_meta.get_mut(&workflow_id).unwrap();
+        let workflow_meta = self
+            .workflows_meta
+            .get_mut(&workflow_id)
+            .ok_or(Error::WorkflowNotFound(workflow_id))?;
         workflow_meta.spawn()
     }
 
@@ -0,0 +1,44 @@
+use geekbar_core::frecency::{Frecency, PickScope};
+use geekbar_store::Store;
+use std::time::Duration;
+use uuid::Uuid;
+
+const NOW: i64 = 100 * 24 * 60 * 60;
+
+fn picked(workflow_id: Uuid, item: &str) -> Frecency {
+    let mut frecency = Frecency::default();
+    frecency.record(workflow_id, PickScope::Trigger, "q", item, NOW);
+    frecency
+}
+
+fn score(store: &Store, workflow_id: Uuid, item: &str) -> f64 {
+    store
+        .load_frecency()
+        .score(workflow_id, PickScope::Trigger, "q", item, NOW)
+}
+
+#[test]
+fn writes_latest_snapshot_once_settled() -> anyhow::Result<()> {
+    let dir = std::env::temp_dir().join(format!("geekbar-store-{}", Uuid::new_v4()));
+    let store = Store::init_in(&dir)?;
+    let workflow_id = Uuid::new_v4();
+
+    let writer = store.frecency_writer(Duration::from_millis(200));
+    writer.save(picked(workflow_id, "first"));
+    writer.save(picked(workflow_id, "second"));
+    // nothing is written while picks keep coming
+    assert_eq!(score(&store, workflow_id, "second"), 0.0);
+
+    std::thread::sleep(Duration::from_secs(1));
+    assert_eq!(score(&store, workflow_id, "first"), 0.0);
+    assert!(score(&store, workflow_id, "second") > 0.0);
+
+    // flushing writes the pending snapshot without waiting
+    let mut writer = store.frecency_writer(Duration::from_secs(60));
+    writer.save(picked(workflow_id, "third"));
+    writer.flush();
+    assert!(score(&store, workflow_id, "third") > 0.0);
+
+    std::fs::remove_dir_all(&dir)?;
+    Ok(())
+}
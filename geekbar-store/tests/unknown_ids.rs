@@ -0,0 +1,40 @@
+use geekbar_core::Error;
+use geekbar_store::Store;
+use uuid::Uuid;
+
+fn temp_store() -> anyhow::Result<Store> {
+    let dir = std::env::temp_dir().join(format!("geekbar-store-{}", Uuid::new_v4()));
+    Store::init_in(&dir)
+}
+
+fn is_workflow_not_found(err: &anyhow::Error, workflow_id: Uuid) -> bool {
+    matches!(err.downcast_ref::<Error>(), Some(Error::WorkflowNotFound(id)) if *id == workflow_id)
+}
+
+#[test]
+fn unknown_workflow_is_not_found() -> anyhow::Result<()> {
+    let mut store = temp_store()?;
+    let workflow_id = Uuid::new_v4();
+
+    let err = store.get_workflow_json(workflow_id).unwrap_err();
+    assert!(is_workflow_not_found(&err, workflow_id));
+
+    let err = store.spawn_workflow(workflow_id).map(|_| ()).unwrap_err();
+    assert!(is_workflow_not_found(&err, workflow_id));
+
+    let err = store.delete_workflow(workflow_id).unwrap_err();
+    assert!(is_workflow_not_found(&err, workflow_id));
+    Ok(())
+}
+
+#[test]
+fn deleted_workflow_is_not_found() -> anyhow::Result<()> {
+    let mut store = temp_store()?;
+    let workflow_id = store.create_workflow("test")?;
+    assert!(store.get_workflow_json(workflow_id).is_ok());
+
+    store.delete_workflow(workflow_id)?;
+    let err = store.delete_workflow(workflow_id).unwrap_err();
+    assert!(is_workflow_not_found(&err, workflow_id));
+    Ok(())
+}
@@ -0,0 +1,138 @@
+use std::{
+    io::Read,
+    sync::{Arc, Mutex},
+    thread::{self, JoinHandle},
+};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tiny_http::{Header, Method, Request, Response, Server};
+
+use crate::Geekbar;
+
+// larger bodies are refused without reading them
+const MAX_BODY_SIZE: usize = 64 * 1024;
+
+/// Body of `POST /trigger`.
+#[derive(Debug, Deserialize)]
+pub struct TriggerRequest {
+    pub trigger_id: String,
+    #[serde(default)]
+    pub params: Option<Value>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(untagged)]
+pub enum TriggerResponse {
+    Matched { matched: bool },
+    Error { error: String },
+}
+
+/// Starts the local trigger endpoint if it is enabled in the config.
+///
+/// ```sh
+/// curl -X POST http://127.0.0.1:37420/trigger \
+///   -H "Authorization: Bearer <token>" \
+///   -d '{"trigger_id": "search", "params": "rust"}'
+/// ```
+pub fn serve(geekbar: Arc<Mutex<Geekbar>>) -> anyhow::Result<Option<JoinHandle<()>>> {
+    let config = geekbar.lock().unwrap().api_config();
+    if !config.enabled {
+        return Ok(None);
+    }
+
+    let server = Server::http(("127.0.0.1", config.port)).map_err(|e| anyhow::anyhow!(e))?;
+    tracing::info!(port = config.port, "api listening");
+
+    let handle = spawn(server, &config.token, move |trigger_id, params| {
+        Ok(geekbar
+            .lock()
+            .unwrap()
+            .executor
+            .trigger(trigger_id, params)?)
+    });
+    Ok(Some(handle))
+}
+
+/// Answers the requests of `server` on a new thread, authorized ones are passed to `trigger`.
+pub fn spawn<F>(server: Server, token: &str, trigger: F) -> JoinHandle<()>
+where
+    F: Fn(&str, Option<Value>) -> anyhow::Result<bool> + Send + 'static,
+{
+    let authorization = format!("Bearer {}", token);
+    thread::spawn(move || {
+        for request in server.incoming_requests() {
+            let (status, result) = handle_request(&trigger, &authorization, request);
+            if let Err(err) = result {
+                tracing::error!(status, %err, "api respond failed");
+            }
+        }
+    })
+}
+
+fn handle_request(
+    trigger: &impl Fn(&str, Option<Value>) -> anyhow::Result<bool>,
+    authorization: &str,
+    mut request: Request,
+) -> (u16, std::io::Result<()>) {
+    let (status, res) = if request.method() != &Method::Post || request.url() != "/trigger" {
+        (404, error("not found"))
+    } else if !is_authorized(&request, authorization) {
+        (401, error("unauthorized"))
+    } else if request.body_length().unwrap_or_default() > MAX_BODY_SIZE {
+        (413, error("body too large"))
+    } else {
+        match read_body(&mut request) {
+            Ok(TriggerRequest { trigger_id, params }) => {
+                tracing::info!(trigger_id, "api trigger");
+                match trigger(&trigger_id, params) {
+                    Ok(matched) => (200, TriggerResponse::Matched { matched }),
+                    Err(err) => (500, error(err)),
+                }
+            }
+            Err(err) => (400, error(err)),
+        }
+    };
+
+    let json = serde_json::to_string(&res).unwrap_or_default();
+    let content_type = Header::from_bytes(&b"Content-Type"[..], &b"application/json"[..])
+        .expect("static header is valid");
+    let response = Response::from_string(json)
+        .with_status_code(status)
+        .with_header(content_type);
+    (status, request.respond(response))
+}
+
+/// Reads at most `MAX_BODY_SIZE` bytes, chunked bodies have no length to check up front.
+fn read_body(request: &mut Request) -> anyhow::Result<TriggerRequest> {
+    let mut body = Vec::new();
+    request
+        .as_reader()
+        .take(MAX_BODY_SIZE as u64 + 1)
+        .read_to_end(&mut body)?;
+    if body.len() > MAX_BODY_SIZE {
+        anyhow::bail!("body too large");
+    }
+    Ok(serde_json::from_slice(&body)?)
+}
+
+fn is_authorized(request: &Request, authorization: &str) -> bool {
+    request
+        .headers()
+        .iter()
+        .find(|header| header.field.equiv("Authorization"))
+        .map(|header| constant_time_eq(header.value.as_str().as_bytes(), authorization.as_bytes()))
+        .unwrap_or(false)
+}
+
+/// Compares without returning at the first different byte, so response times don't leak
+/// how much of the token was guessed right.
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |diff, (a, b)| diff | (a ^ b)) == 0
+}
+
+fn error(err: impl ToString) -> TriggerResponse {
+    TriggerResponse::Error {
+        error: err.to_string(),
+    }
+}
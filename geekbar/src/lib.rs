@@ -1,33 +1,147 @@
-use std::sync::Arc;
+pub mod api;
 
-pub use geekbar_core::executor::{Executor, Work, WorkReceiver};
+use std::sync::Arc;
+use std::time::Duration;
+
+pub use geekbar_core::executor::{
+    CollisionPolicy, Executor, MatchKind, TriggerCandidate, Work, WorkReceiver,
+};
+pub use geekbar_core::frecency::Frecency;
+pub use geekbar_core::hotkey::{Hotkey, HotkeyEvent, HotkeyReceiver};
+pub use geekbar_core::link::Modifier;
+pub use geekbar_core::scheduler::{ScheduleReceiver, ScheduledTrigger};
+use geekbar_core::validator;
+pub use geekbar_core::validator::{Diagnostic, DiagnosticKind, Severity};
+pub use geekbar_core::watcher::{FileWatchEvent, FileWatchReceiver};
 pub use geekbar_core::workflow::Workflow;
 use geekbar_core::workflow::WorkflowConfig;
-use geekbar_store::Store;
+use geekbar_store::{ApiConfig, FrecencyWriter, Store};
+use serde_json::Value;
 use uuid::Uuid;
 
+// picks coming faster than this are saved together
+const FRECENCY_SAVE_DELAY: Duration = Duration::from_secs(2);
+
 pub struct Geekbar {
     store: Store,
     pub executor: Executor,
+    frecency_writer: FrecencyWriter,
 }
 
 impl Geekbar {
     pub fn init() -> anyhow::Result<Self> {
-        let mut executor = Executor::default();
+        Self::init_with(Executor::default())
+    }
+
+    /// Runs nodes on the given tokio runtime, e.g. the one of an async frontend.
+    pub fn init_with_runtime(handle: tokio::runtime::Handle) -> anyhow::Result<Self> {
+        Self::init_with(Executor::with_runtime(handle))
+    }
+
+    fn init_with(mut executor: Executor) -> anyhow::Result<Self> {
         let mut store = Store::init()?;
+        executor.set_collision_policy(store.collision_policy());
+        executor.set_frecency(store.load_frecency());
 
         let workflows = store.fetch_all_workflows()?;
-        executor.add_workflows(workflows);
-
-        let geekbar = Geekbar { store, executor };
+        let frecency_writer = store.frecency_writer(FRECENCY_SAVE_DELAY);
+        let mut geekbar = Geekbar {
+            store,
+            executor,
+            frecency_writer,
+        };
+        for workflow in workflows {
+            geekbar.load_workflow(workflow);
+        }
 
         Ok(geekbar)
     }
 
+    /// Validates the workflow against the loaded ones and loads it unless there are errors,
+    /// failing to load it is an error too.
+    fn load_workflow(&mut self, workflow: Arc<Workflow>) -> Vec<Diagnostic> {
+        let mut diagnostics = validator::validate(
+            &workflow,
+            &self.executor.workflows(),
+            self.executor.collision_policy(),
+        );
+        for diagnostic in &diagnostics {
+            tracing::warn!(workflow_id = %workflow.id, ?diagnostic.kind, diagnostic.message);
+        }
+
+        let workflow_id = workflow.id;
+        if diagnostics.iter().any(Diagnostic::is_error) {
+            tracing::error!(%workflow_id, "workflow not loaded");
+        } else if let Err(err) = self.executor.add_workflow(workflow) {
+            tracing::error!(%workflow_id, %err, "workflow not loaded");
+            diagnostics.push(Diagnostic::error(
+                DiagnosticKind::LoadFailed,
+                Vec::new(),
+                err.to_string(),
+            ));
+        }
+        diagnostics
+    }
+
     pub fn receiver(&self) -> WorkReceiver {
         self.executor.receiver().clone()
     }
 
+    pub fn hotkey_receiver(&self) -> HotkeyReceiver {
+        self.executor.hotkey_receiver().clone()
+    }
+
+    pub fn schedule_receiver(&self) -> ScheduleReceiver {
+        self.executor.schedule_receiver().clone()
+    }
+
+    pub fn file_watch_receiver(&self) -> FileWatchReceiver {
+        self.executor.file_watch_receiver().clone()
+    }
+
+    /// Passes the user's choice on to the workflow, remembering picked options.
+    pub fn receive_work(
+        &self,
+        work: Work,
+        value: Option<Value>,
+        modifiers: &[Modifier],
+    ) -> anyhow::Result<()> {
+        let picked = value.is_some();
+        self.executor.receive_work(work, value, modifiers)?;
+        if picked {
+            self.save_frecency();
+        }
+
+        Ok(())
+    }
+
+    /// Remembers that the keyword was chosen for `query` so it ranks higher next time.
+    pub fn pick_keyword(&self, trigger_id: &str, query: &str) {
+        if self.executor.record_keyword(trigger_id, query) {
+            self.save_frecency();
+        }
+    }
+
+    /// Forgets which keywords and options of the workflow were picked.
+    pub fn reset_frecency(&self, workflow_id: Uuid) {
+        if self.executor.reset_frecency(workflow_id) {
+            self.save_frecency();
+        }
+    }
+
+    fn save_frecency(&self) {
+        self.frecency_writer.save(self.executor.frecency());
+    }
+
+    /// Writes picks that are still waiting to be saved, call it before exiting.
+    pub fn flush_frecency(&mut self) {
+        self.frecency_writer.flush();
+    }
+
+    pub fn api_config(&self) -> ApiConfig {
+        self.store.api_config().clone()
+    }
+
     pub fn fetch_all_workflows(&mut self) -> anyhow::Result<Vec<Arc<Workflow>>> {
         let workflows = self.store.fetch_all_workflows()?;
 
@@ -40,38 +154,65 @@ impl Geekbar {
         Ok(workflows)
     }
 
-    pub fn get_workflow_json(&mut self, workflow_id: Uuid) -> &str {
+    pub fn get_workflow_json(&mut self, workflow_id: Uuid) -> anyhow::Result<&str> {
         self.store.get_workflow_json(workflow_id)
     }
 
     pub fn create_workflow(&mut self, name: &str) -> anyhow::Result<Uuid> {
         let workflow_id = self.store.create_workflow(name)?;
         let workflow = self.store.spawn_workflow(workflow_id)?;
-        self.executor.add_workflow(workflow);
+        self.load_workflow(workflow);
 
         Ok(workflow_id)
     }
 
     pub fn delete_workflow(&mut self, workflow_id: Uuid) -> anyhow::Result<()> {
-        self.executor.remove_workflow(workflow_id);
-        self.store.delete_workflow(workflow_id)
+        // workflows with errors were never loaded
+        if self.executor.has_workflow(workflow_id) {
+            self.executor.remove_workflow(workflow_id)?;
+        }
+        self.store.delete_workflow(workflow_id)?;
+        self.reset_frecency(workflow_id);
+
+        Ok(())
     }
 
-    pub fn save_workflow_json(&mut self, workflow_json: &str) -> anyhow::Result<()> {
+    /// Saves the workflow and returns its diagnostics,
+    /// it is only (re)loaded when there are no errors, otherwise the previous version stays.
+    pub fn save_workflow_json(&mut self, workflow_json: &str) -> anyhow::Result<Vec<Diagnostic>> {
         let workflow_config: WorkflowConfig = serde_json::from_str(workflow_json)?;
         self.save_workflow(workflow_config)
     }
 
-    pub fn save_workflow(&mut self, workflow_config: WorkflowConfig) -> anyhow::Result<()> {
+    pub fn save_workflow(
+        &mut self,
+        workflow_config: WorkflowConfig,
+    ) -> anyhow::Result<Vec<Diagnostic>> {
         let workflow_id = self.store.save_workflow(workflow_config)?;
         tracing::info!(?workflow_id);
 
-        self.executor.remove_workflow(workflow_id);
-
         let workflow = self.store.spawn_workflow(workflow_id)?;
-        self.executor.add_workflow(workflow);
 
-        Ok(())
+        // newly saved workflows aren't loaded yet
+        let previous = self
+            .executor
+            .workflows()
+            .into_iter()
+            .find(|workflow| workflow.id == workflow_id);
+        if previous.is_some() {
+            self.executor.remove_workflow(workflow_id)?;
+        }
+
+        let diagnostics = self.load_workflow(workflow);
+
+        if let Some(previous) = previous {
+            if !self.executor.has_workflow(workflow_id) {
+                tracing::info!(%workflow_id, "previous version kept");
+                self.executor.add_workflow(previous)?;
+            }
+        }
+
+        Ok(diagnostics)
     }
 
     pub fn move_workflow(&mut self, from: usize, to: usize) -> anyhow::Result<()> {
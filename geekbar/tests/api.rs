@@ -0,0 +1,91 @@
+use geekbar::api;
+use serde_json::{json, Value};
+use std::{
+    io::{Read, Write},
+    net::TcpStream,
+    sync::{Arc, Mutex},
+};
+use tiny_http::Server;
+
+const TOKEN: &str = "secret";
+
+// trigger ids and params passed on by the api
+type Triggered = Arc<Mutex<Vec<(String, Option<Value>)>>>;
+
+/// Starts the api on a free port, returns it and the triggered ids.
+fn start() -> (u16, Triggered) {
+    let server = Server::http("127.0.0.1:0").unwrap();
+    let port = server.server_addr().to_ip().unwrap().port();
+    let triggered = Arc::new(Mutex::new(Vec::new()));
+    let calls = triggered.clone();
+    api::spawn(server, TOKEN, move |trigger_id, params| {
+        calls.lock().unwrap().push((trigger_id.to_string(), params));
+        Ok(trigger_id == "search")
+    });
+    (port, triggered)
+}
+
+/// Sends a `POST /trigger`, returns the status code and the body.
+fn post(port: u16, authorization: Option<&str>, body: &str) -> (u16, String) {
+    let mut stream = TcpStream::connect(("127.0.0.1", port)).unwrap();
+    let mut request = format!(
+        "POST /trigger HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\nContent-Length: {}\r\n",
+        body.len()
+    );
+    if let Some(authorization) = authorization {
+        request.push_str(&format!("Authorization: {}\r\n", authorization));
+    }
+    request.push_str("\r\n");
+    request.push_str(body);
+    stream.write_all(request.as_bytes()).unwrap();
+
+    let mut response = String::new();
+    stream.read_to_string(&mut response).unwrap();
+    let status = response[9..12].parse().unwrap();
+    let body = response
+        .split_once("\r\n\r\n")
+        .map(|(_, body)| body.to_string())
+        .unwrap_or_default();
+    (status, body)
+}
+
+#[test]
+fn rejects_wrong_token() {
+    let (port, triggered) = start();
+    let body = json!({ "trigger_id": "search" }).to_string();
+
+    assert_eq!(post(port, None, &body).0, 401);
+    assert_eq!(post(port, Some("Bearer wrong!"), &body).0, 401);
+    assert_eq!(post(port, Some("Bearer secret2"), &body).0, 401);
+    assert_eq!(post(port, Some(TOKEN), &body).0, 401);
+    assert!(triggered.lock().unwrap().is_empty());
+}
+
+#[test]
+fn accepts_token() {
+    let (port, triggered) = start();
+    let body = json!({ "trigger_id": "search", "params": "rust" }).to_string();
+
+    let (status, response) = post(port, Some("Bearer secret"), &body);
+    assert_eq!(status, 200);
+    assert_eq!(
+        serde_json::from_str::<Value>(&response).unwrap(),
+        json!({ "matched": true })
+    );
+    assert_eq!(
+        *triggered.lock().unwrap(),
+        vec![("search".to_string(), Some(json!("rust")))]
+    );
+
+    let (status, _) = post(port, Some("Bearer secret"), "not json");
+    assert_eq!(status, 400);
+}
+
+#[test]
+fn refuses_large_body() {
+    let (port, triggered) = start();
+    let body = json!({ "trigger_id": "search", "params": "x".repeat(128 * 1024) }).to_string();
+
+    assert_eq!(post(port, Some("Bearer secret"), &body).0, 413);
+    assert!(triggered.lock().unwrap().is_empty());
+}